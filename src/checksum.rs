@@ -0,0 +1,151 @@
+use crate::{Error, Result};
+
+// spaces and hyphens are common in card numbers, anything else is a mistake
+fn parse_digits(input: &str) -> Result<Vec<u32>> {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| {
+            c.to_digit(10)
+                .ok_or_else(|| Error::Input(format!("{c:?} is not a digit")))
+        })
+        .collect::<Result<Vec<_>>>()
+        .and_then(|digits| match digits.is_empty() {
+            true => Err(Error::Input("expected some digits".to_string())),
+            false => Ok(digits),
+        })
+}
+
+// doubles every second digit from the right, `offset` shifts which ones for a missing check digit
+fn luhn_sum(digits: &[u32], offset: usize) -> u32 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| match (i + offset) % 2 {
+            1 if *digit > 4 => digit * 2 - 9,
+            1 => digit * 2,
+            _ => *digit,
+        })
+        .sum()
+}
+
+pub fn luhn_is_valid(digits: &[u32]) -> bool {
+    luhn_sum(digits, 0).is_multiple_of(10)
+}
+
+pub fn luhn_check_digit(digits: &[u32]) -> u32 {
+    (10 - luhn_sum(digits, 1) % 10) % 10
+}
+
+pub fn luhn(input: &str) -> Result<String> {
+    Ok(match luhn_is_valid(&parse_digits(input)?) {
+        true => "valid".to_string(),
+        false => "invalid".to_string(),
+    })
+}
+
+pub fn luhn_complete(input: &str) -> Result<String> {
+    let digits = parse_digits(input)?;
+    let check = luhn_check_digit(&digits);
+
+    Ok(digits
+        .iter()
+        .chain([&check])
+        .map(|digit| digit.to_string())
+        .collect())
+}
+
+// isbn-10 allows an X for a check digit of 10
+fn parse_isbn(input: &str) -> Result<Vec<u32>> {
+    let stripped = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>();
+
+    let digits = match stripped.strip_suffix(['X', 'x']) {
+        Some(rest) if rest.len() == 9 => [parse_digits(rest)?, vec![10]].concat(),
+        _ => parse_digits(&stripped)?,
+    };
+
+    match digits.len() {
+        10 | 13 => Ok(digits),
+        n => Err(Error::Input(format!(
+            "an isbn has 10 or 13 digits, not {n}"
+        ))),
+    }
+}
+
+// weighted 10 down to 2, mod 11
+pub fn isbn10_check_digit(digits: &[u32]) -> u32 {
+    let sum = digits
+        .iter()
+        .zip((2..=10).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum::<u32>();
+
+    (11 - sum % 11) % 11
+}
+
+// weighted 1 and 3 alternately, mod 10
+pub fn isbn13_check_digit(digits: &[u32]) -> u32 {
+    let sum = digits
+        .iter()
+        .zip([1, 3].into_iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum::<u32>();
+
+    (10 - sum % 10) % 10
+}
+
+// errors on a bad check digit, otherwise prints the isbn-13 form
+pub fn isbn(input: &str) -> Result<String> {
+    let digits = parse_isbn(input)?;
+    let (body, check) = digits.split_at(digits.len() - 1);
+
+    let expected = match body.len() {
+        9 => isbn10_check_digit(body),
+        _ => isbn13_check_digit(body),
+    };
+
+    if check[0] != expected {
+        let expected = match expected {
+            10 => "X".to_string(),
+            digit => digit.to_string(),
+        };
+        return Err(Error::Input(format!(
+            "invalid isbn, the check digit should be {expected}"
+        )));
+    }
+
+    let body = match body.len() {
+        9 => [&[9, 7, 8], body].concat(),
+        _ => body.to_vec(),
+    };
+    let check = isbn13_check_digit(&body);
+
+    Ok(body
+        .iter()
+        .chain([&check])
+        .map(|digit| digit.to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isbn_converts_valid_numbers() {
+        assert_eq!(isbn("0-306-40615-2").unwrap(), "9780306406157");
+        assert_eq!(isbn("080442957X").unwrap(), "9780804429573");
+        assert_eq!(isbn("978-0-306-40615-7").unwrap(), "9780306406157");
+    }
+
+    #[test]
+    fn isbn_rejects_wrong_check_digits() {
+        assert!(isbn("0-306-40615-3").is_err());
+        assert!(isbn("9780306406158").is_err());
+        assert!(isbn("12345").is_err());
+    }
+}
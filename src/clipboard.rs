@@ -0,0 +1,171 @@
+use copypasta::ClipboardProvider;
+use narigama_utils::{Error, Result};
+use std::io::Write;
+
+pub trait Clipboard {
+    fn get(&mut self) -> Result<String>;
+    fn set(&mut self, contents: &str) -> Result<()>;
+}
+
+impl Clipboard for copypasta::ClipboardContext {
+    fn get(&mut self) -> Result<String> {
+        self.get_contents().map_err(Error::Clipboard)
+    }
+
+    fn set(&mut self, contents: &str) -> Result<()> {
+        self.set_contents(contents.to_string())
+            .map_err(Error::Clipboard)
+    }
+}
+
+// an external tool, for when copypasta can't reach the clipboard like under wayland or wsl
+#[derive(Debug, Clone, Copy)]
+struct CommandClipboard {
+    get: &'static [&'static str],
+    set: &'static [&'static str],
+    // the session a tool needs, being installed on a headless box doesn't make it usable
+    display: Option<&'static str>,
+}
+
+const COMMAND_CLIPBOARDS: [(&str, CommandClipboard); 4] = [
+    (
+        "wayland",
+        CommandClipboard {
+            get: &["wl-paste", "--no-newline"],
+            set: &["wl-copy"],
+            display: Some("WAYLAND_DISPLAY"),
+        },
+    ),
+    (
+        "xclip",
+        CommandClipboard {
+            get: &["xclip", "-selection", "clipboard", "-out"],
+            set: &["xclip", "-selection", "clipboard", "-in"],
+            display: Some("DISPLAY"),
+        },
+    ),
+    (
+        "xsel",
+        CommandClipboard {
+            get: &["xsel", "--clipboard", "--output"],
+            set: &["xsel", "--clipboard", "--input"],
+            display: Some("DISPLAY"),
+        },
+    ),
+    (
+        "wsl",
+        CommandClipboard {
+            get: &["win32yank.exe", "-o", "--lf"],
+            set: &["win32yank.exe", "-i", "--crlf"],
+            display: None,
+        },
+    ),
+];
+
+fn command_failed(program: &str, reason: impl std::fmt::Display) -> Error {
+    Error::Clipboard(format!("{program} failed, {reason}").into())
+}
+
+impl Clipboard for CommandClipboard {
+    fn get(&mut self) -> Result<String> {
+        let output = std::process::Command::new(self.get[0])
+            .args(&self.get[1..])
+            .stderr(std::process::Stdio::null())
+            .output()
+            .map_err(|err| command_failed(self.get[0], err))?;
+
+        match output.status.success() {
+            true => Ok(String::from_utf8(output.stdout)?),
+            false => Err(command_failed(self.get[0], output.status)),
+        }
+    }
+
+    // the tools fork to keep serving the clipboard, so they mustn't hold on to our stdout
+    fn set(&mut self, contents: &str) -> Result<()> {
+        let mut child = std::process::Command::new(self.set[0])
+            .args(&self.set[1..])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map_err(|err| command_failed(self.set[0], err))?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(contents.as_bytes())?;
+
+        match child.wait()? {
+            status if status.success() => Ok(()),
+            status => Err(command_failed(self.set[0], status)),
+        }
+    }
+}
+
+fn is_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+impl CommandClipboard {
+    fn is_usable(&self) -> bool {
+        self.display
+            .is_none_or(|display| std::env::var_os(display).is_some_and(|value| !value.is_empty()))
+            && is_on_path(self.get[0])
+    }
+}
+
+fn copypasta() -> Option<Box<dyn Clipboard>> {
+    copypasta::ClipboardContext::new()
+        .inspect(|_| tracing::debug!(backend = "copypasta", "using the system clipboard"))
+        .inspect_err(|err| tracing::debug!(%err, "copypasta is unavailable"))
+        .ok()
+        .map(|clipboard| Box::new(clipboard) as Box<dyn Clipboard>)
+}
+
+fn command_clipboard(name: &str, clipboard: &CommandClipboard) -> Box<dyn Clipboard> {
+    tracing::debug!(backend = name, "using a command line clipboard");
+    Box::new(*clipboard)
+}
+
+fn named_clipboard(name: &str) -> Option<Box<dyn Clipboard>> {
+    COMMAND_CLIPBOARDS
+        .iter()
+        .find(|(backend, _)| *backend == name)
+        .map(|(name, clipboard)| command_clipboard(name, clipboard))
+}
+
+// wayland only sessions have no x server for copypasta to talk to, so wl-clipboard goes first,
+// then copypasta, then whichever other tool is installed
+fn detect_clipboard() -> Option<Box<dyn Clipboard>> {
+    let usable = |name: &str| {
+        COMMAND_CLIPBOARDS
+            .iter()
+            .find(|(backend, clipboard)| *backend == name && clipboard.is_usable())
+            .map(|(name, clipboard)| command_clipboard(name, clipboard))
+    };
+
+    usable("wayland").or_else(copypasta).or_else(|| {
+        COMMAND_CLIPBOARDS
+            .iter()
+            .find(|(_, clipboard)| clipboard.is_usable())
+            .map(|(name, clipboard)| command_clipboard(name, clipboard))
+    })
+}
+
+// NARIGAMA_CLIPBOARD picks a backend by name, none means read stdin and only print the result
+pub fn select_clipboard() -> Option<Box<dyn Clipboard>> {
+    let backend = std::env::var("NARIGAMA_CLIPBOARD").ok();
+    tracing::debug!(?backend, "opening clipboard");
+
+    match backend.as_deref() {
+        None | Some("") => detect_clipboard(),
+        Some("none") => None,
+        Some("copypasta") => copypasta(),
+        Some(name) => named_clipboard(name).or_else(|| {
+            eprintln!("warning: {name:?} is not a known clipboard, detecting one instead");
+            detect_clipboard()
+        }),
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+    // 0.0 to 1.0, none when the input didn't specify one
+    a: Option<f64>,
+}
+
+fn parse_hex(input: &str) -> Option<Color> {
+    let hex = input.strip_prefix('#').unwrap_or(input);
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    // #rgb and #rgba double up each digit
+    let expanded = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+
+    let channels = (0..expanded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&expanded[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+
+    Some(Color {
+        r: channels[0],
+        g: channels[1],
+        b: channels[2],
+        a: channels.get(3).map(|a| *a as f64 / 255.0),
+    })
+}
+
+// accepts both `rgb(255, 136, 0)` and the newer `rgb(255 136 0 / 50%)`
+fn parse_function(input: &str) -> Option<(String, Vec<&str>)> {
+    let (name, rest) = input.split_once('(')?;
+    let args = rest.strip_suffix(')')?;
+
+    let args = args
+        .split([',', ' ', '/'])
+        .filter(|arg| !arg.is_empty())
+        .collect();
+
+    Some((name.trim().to_lowercase(), args))
+}
+
+// a plain number, or a percentage of `scale`
+fn parse_component(input: &str, scale: f64) -> Option<f64> {
+    match input.strip_suffix('%') {
+        Some(percent) => Some(percent.parse::<f64>().ok()? / 100.0 * scale),
+        None => input.parse().ok(),
+    }
+}
+
+fn parse_alpha(input: Option<&&str>) -> Option<Option<f64>> {
+    match input {
+        Some(alpha) => Some(Some(parse_component(alpha, 1.0)?.clamp(0.0, 1.0))),
+        None => Some(None),
+    }
+}
+
+fn channel(value: f64) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let h = h.rem_euclid(360.0);
+    let s = s.clamp(0.0, 1.0);
+    let l = l.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = chroma * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - chroma / 2.0;
+
+    let (r, g, b) = match h {
+        h if h < 60.0 => (chroma, x, 0.0),
+        h if h < 120.0 => (x, chroma, 0.0),
+        h if h < 180.0 => (0.0, chroma, x),
+        h if h < 240.0 => (0.0, x, chroma),
+        h if h < 300.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    (
+        channel((r + m) * 255.0),
+        channel((g + m) * 255.0),
+        channel((b + m) * 255.0),
+    )
+}
+
+fn rgb_to_hsl(color: &Color) -> (f64, f64, f64) {
+    let r = color.r as f64 / 255.0;
+    let g = color.g as f64 / 255.0;
+    let b = color.b as f64 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let l = (max + min) / 2.0;
+
+    if delta == 0.0 {
+        return (0.0, 0.0, l);
+    }
+
+    let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+    let h = match max {
+        max if max == r => 60.0 * ((g - b) / delta).rem_euclid(6.0),
+        max if max == g => 60.0 * ((b - r) / delta + 2.0),
+        _ => 60.0 * ((r - g) / delta + 4.0),
+    };
+
+    (h, s, l)
+}
+
+fn parse_color(input: &str) -> Result<Color> {
+    let input = input.trim();
+    let invalid = || Error::Input(format!("{input:?} is not a hex, rgb() or hsl() color"));
+
+    if let Some(color) = parse_hex(input) {
+        return Ok(color);
+    }
+
+    let (name, args) = parse_function(input).ok_or_else(invalid)?;
+
+    if !(3..=4).contains(&args.len()) {
+        return Err(invalid());
+    }
+
+    match name.as_str() {
+        "rgb" | "rgba" => Ok(Color {
+            r: channel(parse_component(args[0], 255.0).ok_or_else(invalid)?),
+            g: channel(parse_component(args[1], 255.0).ok_or_else(invalid)?),
+            b: channel(parse_component(args[2], 255.0).ok_or_else(invalid)?),
+            a: parse_alpha(args.get(3)).ok_or_else(invalid)?,
+        }),
+        "hsl" | "hsla" => {
+            let h = args[0].trim_end_matches("deg").parse::<f64>();
+            let s = parse_component(args[1].trim_end_matches('%'), 1.0);
+            let l = parse_component(args[2].trim_end_matches('%'), 1.0);
+
+            let (r, g, b) = match (h, s, l) {
+                (Ok(h), Some(s), Some(l)) => hsl_to_rgb(h, s / 100.0, l / 100.0),
+                _ => return Err(invalid()),
+            };
+
+            Ok(Color {
+                r,
+                g,
+                b,
+                a: parse_alpha(args.get(3)).ok_or_else(invalid)?,
+            })
+        }
+        _ => Err(invalid()),
+    }
+}
+
+// alpha is shown with at most two decimals, 1 and 0.5 rather than 1.00 and 0.50
+fn format_alpha(alpha: f64) -> f64 {
+    (alpha * 100.0).round() / 100.0
+}
+
+// hue is rounded to whole degrees and saturation/lightness to whole percentages, which is
+// what most design tools show, so converting hsl back to rgb can be off by one per channel
+pub fn convert_color(input: &str) -> Result<String> {
+    let color = parse_color(input)?;
+    let (h, s, l) = rgb_to_hsl(&color);
+    let (h, s, l) = (h.round() % 360.0, (s * 100.0).round(), (l * 100.0).round());
+
+    let (hex, rgb, hsl) = match color.a {
+        Some(a) => (
+            format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                color.r,
+                color.g,
+                color.b,
+                channel(a * 255.0)
+            ),
+            format!(
+                "rgba({}, {}, {}, {})",
+                color.r,
+                color.g,
+                color.b,
+                format_alpha(a)
+            ),
+            format!("hsla({h}, {s}%, {l}%, {})", format_alpha(a)),
+        ),
+        None => (
+            format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b),
+            format!("rgb({}, {}, {})", color.r, color.g, color.b),
+            format!("hsl({h}, {s}%, {l}%)"),
+        ),
+    };
+
+    Ok(format!("{hex}\n{rgb}\n{hsl}"))
+}
@@ -0,0 +1,329 @@
+use crate::{Error, Result, now};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+// crontab counts from sunday as 0, and also accepts 7 for sunday
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const CRON_SHORTHANDS: [(&str, &str); 7] = [
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+    ("@monthly", "0 0 1 * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@daily", "0 0 * * *"),
+    ("@midnight", "0 0 * * *"),
+    ("@hourly", "0 * * * *"),
+];
+
+const CRON_UPCOMING: usize = 5;
+
+struct CronField {
+    unit: &'static str,
+    units: &'static str,
+    names: &'static [&'static str],
+    // the value of names[0]
+    first: u32,
+}
+
+const SECOND: CronField = CronField {
+    unit: "second",
+    units: "seconds",
+    names: &[],
+    first: 0,
+};
+const MINUTE: CronField = CronField {
+    unit: "minute",
+    units: "minutes",
+    names: &[],
+    first: 0,
+};
+const HOUR: CronField = CronField {
+    unit: "hour",
+    units: "hours",
+    names: &[],
+    first: 0,
+};
+const DAY_OF_MONTH: CronField = CronField {
+    unit: "day-of-month",
+    units: "days-of-month",
+    names: &[],
+    first: 1,
+};
+const MONTH: CronField = CronField {
+    unit: "month",
+    units: "months",
+    names: &MONTHS,
+    first: 1,
+};
+const DAY_OF_WEEK: CronField = CronField {
+    unit: "day-of-week",
+    units: "days-of-week",
+    names: &WEEKDAYS,
+    first: 0,
+};
+
+fn is_any(field: &str) -> bool {
+    field == "*" || field == "?"
+}
+
+fn ordinal(n: &str) -> String {
+    let suffix = match n.parse::<u32>().unwrap_or_default() % 100 {
+        11..=13 => "th",
+        n if n % 10 == 1 => "st",
+        n if n % 10 == 2 => "nd",
+        n if n % 10 == 3 => "rd",
+        _ => "th",
+    };
+
+    format!("{n}{suffix}")
+}
+
+// names are matched on their first three letters, like crontab does
+fn value_name(field: &CronField, value: &str) -> String {
+    if field.names.is_empty() {
+        return value.to_string();
+    }
+
+    let index = match value.parse::<u32>() {
+        Ok(n) => n
+            .checked_sub(field.first)
+            .map(|n| n as usize % field.names.len()),
+        Err(_) => field.names.iter().position(|name| {
+            value.len() >= 3 && name.to_lowercase().starts_with(&value.to_lowercase())
+        }),
+    };
+
+    index
+        .map(|index| field.names[index].to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn join_and(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+// none when the field matches everything
+fn describe_field(field: &CronField, input: &str) -> Option<String> {
+    if is_any(input) {
+        return None;
+    }
+
+    let mut points = Vec::new();
+    let mut phrases = Vec::new();
+
+    for item in input.split(',') {
+        let (base, step) = match item.split_once('/') {
+            Some((base, step)) => (base, Some(ordinal(step))),
+            None => (item, None),
+        };
+        let every = match &step {
+            Some(step) if step != "1st" => format!("every {step} {}", field.unit),
+            _ => format!("every {}", field.unit),
+        };
+
+        match (base.split_once('-'), step) {
+            (Some((start, end)), _) => phrases.push(format!(
+                "{every} from {} through {}",
+                value_name(field, start),
+                value_name(field, end)
+            )),
+            (None, Some(_)) if is_any(base) => phrases.push(every),
+            (None, Some(_)) => phrases.push(format!("{every} from {}", value_name(field, base))),
+            (None, None) => points.push(value_name(field, base)),
+        }
+    }
+
+    match (field.names.is_empty(), points.len()) {
+        (_, 0) => {}
+        (false, _) => phrases.insert(0, join_and(&points)),
+        (true, 1) => phrases.insert(0, format!("{} {}", field.unit, points[0])),
+        (true, _) => phrases.insert(0, format!("{} {}", field.units, join_and(&points))),
+    }
+
+    Some(phrases.join(" and "))
+}
+
+fn is_number(field: &str) -> bool {
+    !field.is_empty() && field.chars().all(|c| c.is_ascii_digit())
+}
+
+fn describe(second: Option<&str>, fields: &[&str; 5]) -> String {
+    let [minute, hour, day_of_month, month, day_of_week] = *fields;
+    let second = second.filter(|second| *second != "0");
+
+    let time = match (second, is_number(minute) && is_number(hour)) {
+        (None, true) => format!("At {hour:0>2}:{minute:0>2}"),
+        (Some(second), true) if is_number(second) => {
+            format!("At {hour:0>2}:{minute:0>2}:{second:0>2}")
+        }
+        _ => {
+            let second = second.and_then(|second| describe_field(&SECOND, second));
+            let minute = match (&second, describe_field(&MINUTE, minute)) {
+                (None, None) => Some("every minute".to_string()),
+                (_, minute) => minute,
+            };
+
+            let clauses = [second, minute, describe_field(&HOUR, hour)]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            format!("At {}", clauses.join(" past "))
+        }
+    };
+
+    let day_of_month = describe_field(&DAY_OF_MONTH, day_of_month);
+    let month = describe_field(&MONTH, month);
+    let day_of_week = describe_field(&DAY_OF_WEEK, day_of_week);
+
+    // when both are restricted crontab runs on either, not only when both match
+    let days = match (day_of_month, day_of_week) {
+        (Some(day_of_month), Some(day_of_week)) => {
+            format!(" on {day_of_month} or {day_of_week}")
+        }
+        (Some(day), None) | (None, Some(day)) => format!(" on {day}"),
+        (None, None) if month.is_none() && !is_any(hour) => " every day".to_string(),
+        (None, None) => String::new(),
+    };
+    let month = month
+        .map(|month| format!(" in {month}"))
+        .unwrap_or_default();
+
+    format!("{time}{days}{month}")
+}
+
+// jiff-cron counts weekdays from 1 for sunday, so crontab numbers are swapped for names first
+fn crontab_day_of_week(input: &str) -> String {
+    let name = |value: &str| match value.parse::<usize>() {
+        Ok(n @ 0..=7) => WEEKDAYS[n % 7][..3].to_string(),
+        _ => value.to_string(),
+    };
+
+    input
+        .split(',')
+        .map(|item| {
+            let (base, step) = match item.split_once('/') {
+                Some((base, step)) => (base, format!("/{step}")),
+                None => (item, String::new()),
+            };
+
+            match base.split_once('-') {
+                // sunday as 7 would end the range before it starts
+                Some((start, "7")) if step.is_empty() && start != "0" && start != "7" => {
+                    format!("{}-Sat,Sun", name(start))
+                }
+                Some((start, end)) => format!("{}-{}{step}", name(start), name(end)),
+                None => format!("{}{step}", name(base)),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn schedule(second: &str, fields: &[&str; 5]) -> Result<jiff_cron::Schedule> {
+    let [minute, hour, day_of_month, month, day_of_week] = *fields;
+    let expression = format!(
+        "{second} {minute} {hour} {day_of_month} {month} {}",
+        crontab_day_of_week(day_of_week)
+    );
+
+    // the error points at the rewritten expression, only the last line makes sense on its own
+    expression.parse().map_err(|err: jiff_cron::error::Error| {
+        let err = err.to_string();
+        Error::Input(format!(
+            "not a valid cron expression, {}",
+            err.lines().last().unwrap_or_default()
+        ))
+    })
+}
+
+fn upcoming(second: &str, fields: &[&str; 5]) -> Result<Vec<jiff::Zoned>> {
+    let [minute, hour, day_of_month, month, day_of_week] = *fields;
+    let after = now();
+
+    // crontab ors the two day fields together when both are given, jiff-cron ands them, so each
+    // is scheduled on its own and the results merged
+    let mut times = match is_any(day_of_month) || is_any(day_of_week) {
+        true => schedule(second, fields)?
+            .after(after)
+            .take(CRON_UPCOMING)
+            .collect::<Vec<_>>(),
+        false => {
+            let by_month = schedule(second, &[minute, hour, day_of_month, month, "*"])?;
+            let by_week = schedule(second, &[minute, hour, "*", month, day_of_week])?;
+
+            by_month
+                .after(after.clone())
+                .take(CRON_UPCOMING)
+                .chain(by_week.after(after).take(CRON_UPCOMING))
+                .collect()
+        }
+    };
+
+    times.sort();
+    times.dedup();
+    times.truncate(CRON_UPCOMING);
+    Ok(times)
+}
+
+// standard five field crontab lines, or six with seconds first
+pub fn describe_cron(input: &str) -> Result<String> {
+    let input = input.trim();
+    let expanded = CRON_SHORTHANDS
+        .iter()
+        .find(|(shorthand, _)| input.eq_ignore_ascii_case(shorthand))
+        .map(|(_, expression)| *expression)
+        .unwrap_or(input);
+
+    let fields = expanded.split_whitespace().collect::<Vec<_>>();
+    let (second, fields) = match fields.as_slice() {
+        [minute, hour, day_of_month, month, day_of_week] => {
+            (None, [*minute, *hour, *day_of_month, *month, *day_of_week])
+        }
+        [second, minute, hour, day_of_month, month, day_of_week] => (
+            Some(*second),
+            [*minute, *hour, *day_of_month, *month, *day_of_week],
+        ),
+        _ => {
+            return Err(Error::Input(format!(
+                "{input:?} has {} fields, expected 5 or 6",
+                fields.len()
+            )));
+        }
+    };
+
+    let times = upcoming(second.unwrap_or("0"), &fields)?
+        .iter()
+        .map(|time| time.timestamp().to_string())
+        .collect::<Vec<_>>();
+
+    Ok(format!(
+        "{}\n\nnext runs (utc):\n{}",
+        describe(second, &fields),
+        times.join("\n")
+    ))
+}
@@ -0,0 +1,737 @@
+use crate::{Error, Result, to_hex};
+use base64::Engine;
+use std::io::{Read, Write};
+
+const BASE64_STANDARD: base64::engine::GeneralPurpose = base64::engine::GeneralPurpose::new(
+    &base64::alphabet::STANDARD,
+    base64::engine::GeneralPurposeConfig::new()
+        .with_encode_padding(true)
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+);
+
+const BASE64_URL_SAFE: base64::engine::GeneralPurpose = base64::engine::GeneralPurpose::new(
+    &base64::alphabet::URL_SAFE,
+    base64::engine::GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+);
+
+fn base64_engine(url_safe: bool) -> &'static base64::engine::GeneralPurpose {
+    match url_safe {
+        true => &BASE64_URL_SAFE,
+        false => &BASE64_STANDARD,
+    }
+}
+
+pub fn base64_decode(input: &str, url_safe: bool) -> Result<String> {
+    let bytes = base64_engine(url_safe).decode(input.trim())?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+pub fn base64_encode(input: &str, url_safe: bool) -> String {
+    base64_engine(url_safe).encode(input.as_bytes())
+}
+
+pub fn data_uri(input: &str, mime: &str) -> Result<String> {
+    let mime = mime.trim();
+    if mime
+        .split_once('/')
+        .is_none_or(|(kind, subtype)| kind.is_empty() || subtype.is_empty())
+        || mime.contains([',', ' '])
+    {
+        return Err(Error::Input(format!(
+            "{mime:?} is not a mime type like text/plain"
+        )));
+    }
+
+    Ok(format!(
+        "data:{mime};base64,{}",
+        BASE64_STANDARD.encode(input.as_bytes())
+    ))
+}
+
+// rfc 2397, anything not marked base64 is percent-encoded instead
+pub fn data_uri_decode(input: &str) -> Result<String> {
+    let input = input.trim();
+    let (header, data) = input
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(|| {
+            Error::Input("expected a data uri like data:text/plain;base64,...".to_string())
+        })?;
+
+    let bytes = match header.ends_with(";base64") {
+        true => BASE64_STANDARD.decode(data)?,
+        false => percent_encoding::percent_decode_str(data).collect(),
+    };
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+// rfc 4648, lowercase and missing padding are both common in totp secrets
+pub(crate) fn base32_decode_bytes(input: &str) -> Result<Vec<u8>> {
+    let normalised = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '=')
+        .collect::<String>()
+        .to_uppercase();
+
+    Ok(data_encoding::BASE32_NOPAD.decode(normalised.as_bytes())?)
+}
+
+pub fn base32_decode(input: &str) -> Result<String> {
+    Ok(String::from_utf8(base32_decode_bytes(input)?)?)
+}
+
+pub fn base32_encode(input: &str) -> String {
+    data_encoding::BASE32.encode(input.as_bytes())
+}
+
+// bitcoin alphabet, `check` appends/verifies a 4 byte double sha256 checksum (base58check)
+pub fn base58_decode(input: &str, check: bool) -> Result<String> {
+    let decoder = bs58::decode(input.trim());
+    let bytes = match check {
+        true => decoder.with_check(None).into_vec()?,
+        false => decoder.into_vec()?,
+    };
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+pub fn base58_encode(input: &str, check: bool) -> String {
+    let encoder = bs58::encode(input.as_bytes());
+    match check {
+        true => encoder.with_check().into_string(),
+        false => encoder.into_string(),
+    }
+}
+
+pub fn gzip_pack(input: &str) -> Result<String> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(input.as_bytes())?;
+
+    Ok(BASE64_STANDARD.encode(encoder.finish()?))
+}
+
+pub fn gzip_unpack(input: &str) -> Result<String> {
+    // pasted blobs are often wrapped across lines
+    let input = input.split_whitespace().collect::<String>();
+    let bytes = BASE64_STANDARD.decode(input)?;
+
+    let mut output = Vec::new();
+    flate2::read::GzDecoder::new(bytes.as_slice())
+        .read_to_end(&mut output)
+        .map_err(|err| Error::Input(format!("not valid gzip data, {err}")))?;
+
+    Ok(String::from_utf8(output)?)
+}
+
+pub fn binary_decode(input: &str) -> Result<String> {
+    // split_whitespace skips the empty chunks left by double spaces or newlines
+    let bytes = input
+        .split_whitespace()
+        .map(
+            |chunk| match chunk.len() <= 8 && chunk.chars().all(|c| c == '0' || c == '1') {
+                true => Ok(u8::from_str_radix(chunk, 2)?),
+                false => Err(Error::Input(format!(
+                    "invalid binary chunk {chunk:?}, expected up to 8 bits of 0 or 1"
+                ))),
+            },
+        )
+        .collect::<Result<Vec<u8>>>()?;
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+// without an explicit base, a 0x, 0b or 0o prefix picks one and anything else is decimal
+fn parse_number(input: &str, from: Option<u32>) -> Result<u128> {
+    let input = input.trim().replace('_', "");
+    let lowered = input.to_lowercase();
+
+    let (digits, radix) = match (from, lowered.get(..2)) {
+        (Some(radix), _) => (lowered.as_str(), radix),
+        (None, Some("0x")) => (&lowered[2..], 16),
+        (None, Some("0b")) => (&lowered[2..], 2),
+        (None, Some("0o")) => (&lowered[2..], 8),
+        (None, _) => (lowered.as_str(), 10),
+    };
+
+    u128::from_str_radix(digits, radix).map_err(|err| match err.kind() {
+        std::num::IntErrorKind::PosOverflow => Error::Input(format!(
+            "{input:?} is too large, the maximum is {}",
+            u128::MAX
+        )),
+        _ => Error::Input(format!("{input:?} is not a valid base {radix} number")),
+    })
+}
+
+fn format_radix(mut value: u128, radix: u32) -> String {
+    let mut digits = Vec::new();
+
+    loop {
+        digits.push(char::from_digit((value % radix as u128) as u32, radix).unwrap());
+        value /= radix as u128;
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    digits.iter().rev().collect()
+}
+
+pub fn base_convert(input: &str, from: Option<u32>, to: Option<u32>) -> Result<String> {
+    let value = parse_number(input, from)?;
+
+    Ok(match to {
+        Some(radix) => format_radix(value, radix),
+        None => format!("bin: {value:#b}\noct: {value:#o}\ndec: {value}\nhex: {value:#x}"),
+    })
+}
+
+const ROMAN_NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+pub fn to_roman(mut value: u32) -> Result<String> {
+    if !(1..=3999).contains(&value) {
+        return Err(Error::Input(format!(
+            "{value} is out of range, roman numerals cover 1 to 3999"
+        )));
+    }
+
+    let mut output = String::new();
+    for (amount, numeral) in ROMAN_NUMERALS {
+        while value >= amount {
+            output.push_str(numeral);
+            value -= amount;
+        }
+    }
+
+    Ok(output)
+}
+
+// greedily matches the largest numeral, then round trips to reject things like IIII or VX
+pub fn from_roman(input: &str) -> Result<u32> {
+    let numeral = input.trim().to_uppercase();
+    let invalid = || Error::Input(format!("{:?} is not a valid roman numeral", input.trim()));
+
+    let mut rest = numeral.as_str();
+    let mut value = 0;
+    while !rest.is_empty() {
+        let (amount, symbol) = ROMAN_NUMERALS
+            .iter()
+            .find(|(_, symbol)| rest.starts_with(symbol))
+            .ok_or_else(invalid)?;
+
+        value += amount;
+        rest = &rest[symbol.len()..];
+    }
+
+    match to_roman(value) {
+        Ok(canonical) if canonical == numeral => Ok(value),
+        _ => Err(invalid()),
+    }
+}
+
+// numbers become numerals and numerals become numbers
+pub fn roman(input: &str) -> Result<String> {
+    match input.trim().parse::<u32>() {
+        Ok(value) => to_roman(value),
+        Err(_) => Ok(from_roman(input)?.to_string()),
+    }
+}
+
+// the digits are kept exactly as given, so there's no float rounding to worry about
+pub fn number_format(input: &str, separator: &str, decimal: &str) -> Result<String> {
+    let input = input.trim();
+    let invalid = || Error::Input(format!("{input:?} is not a number"));
+
+    let (sign, unsigned) = match input.chars().next() {
+        Some('-') => ("-", &input[1..]),
+        Some('+') => ("", &input[1..]),
+        _ => ("", input),
+    };
+
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty() || !is_digits(integer) || !fraction.is_none_or(is_digits) {
+        return Err(invalid());
+    }
+
+    let grouped = integer
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|group| std::str::from_utf8(group).unwrap())
+        .collect::<Vec<_>>()
+        .join(separator);
+
+    Ok(match fraction {
+        Some(fraction) => format!("{sign}{grouped}{decimal}{fraction}"),
+        None => format!("{sign}{grouped}"),
+    })
+}
+
+pub fn binary_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| format!("{:0>8}", format!("{b:b}")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+const MORSE: [(char, &str); 54] = [
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
+
+pub fn morse_decode(input: &str) -> Result<String> {
+    input
+        .split('/')
+        .map(|word| {
+            word.split_whitespace()
+                .map(|code| {
+                    MORSE
+                        .iter()
+                        .find(|(_, c)| *c == code)
+                        .map(|(letter, _)| *letter)
+                        .ok_or_else(|| Error::Input(format!("unknown morse code {code:?}")))
+                })
+                .collect::<Result<String>>()
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|words| words.join(" ").trim().to_string())
+}
+
+// characters without a morse equivalent are dropped
+pub fn morse_encode(input: &str) -> String {
+    input
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter_map(|c| {
+                    MORSE
+                        .iter()
+                        .find(|(letter, _)| *letter == c.to_ascii_uppercase())
+                        .map(|(_, code)| *code)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+pub(crate) fn hex_decode_bytes(input: &str) -> Result<Vec<u8>> {
+    // accept "de ad", "de:ad" and "0xdead" alike
+    let digits = input
+        .split_whitespace()
+        .flat_map(|chunk| chunk.split(':'))
+        .map(|chunk| chunk.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect::<String>();
+
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(Error::Input(format!("invalid hex digit {c:?}")));
+    }
+
+    if digits.len() % 2 != 0 {
+        return Err(Error::Input(format!(
+            "hex input has an odd number of digits ({})",
+            digits.len()
+        )));
+    }
+
+    Ok(digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect())
+}
+
+pub fn hex_decode(input: &str) -> Result<String> {
+    Ok(String::from_utf8(hex_decode_bytes(input)?)?)
+}
+
+pub fn hex_encode(input: &str, separator: &str) -> String {
+    input
+        .bytes()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// the `xxd`/`hexdump -C` layout, sixteen bytes a row split into two columns of eight
+pub fn hexdump(input: &str) -> String {
+    input
+        .as_bytes()
+        .chunks(16)
+        .enumerate()
+        .map(|(row, bytes)| {
+            let hex = bytes
+                .chunks(8)
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|b| format!("{b:02x}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            let ascii = bytes
+                .iter()
+                .map(|b| match b.is_ascii_graphic() || *b == b' ' {
+                    true => *b as char,
+                    false => '.',
+                })
+                .collect::<String>();
+
+            format!("{:08x}  {hex:<48}  |{ascii}|", row * 16)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// the payload is binary, so it's read and shown as hex
+pub fn bech32_encode(input: &str, hrp: &str, bech32m: bool) -> Result<String> {
+    let hrp = bech32::Hrp::parse(hrp)
+        .map_err(|err| Error::Input(format!("{hrp:?} is not a valid prefix, {err}")))?;
+    let data = hex_decode_bytes(input)?;
+
+    let encoded = match bech32m {
+        true => bech32::encode::<bech32::Bech32m>(hrp, &data),
+        false => bech32::encode::<bech32::Bech32>(hrp, &data),
+    };
+
+    encoded.map_err(|err| Error::Input(err.to_string()))
+}
+
+const SEGWIT_HRPS: [&str; 3] = ["bc", "tb", "bcrt"];
+
+// either checksum variant is accepted, bitcoin addresses start with a witness version that
+// isn't part of the program so they're decoded separately
+pub fn bech32_decode(input: &str) -> Result<String> {
+    let input = input.trim();
+    let is_segwit = input.rsplit_once('1').is_some_and(|(hrp, _)| {
+        SEGWIT_HRPS
+            .iter()
+            .any(|segwit| hrp.eq_ignore_ascii_case(segwit))
+    });
+
+    if is_segwit && let Ok((hrp, version, program)) = bech32::segwit::decode(input) {
+        return Ok(format!(
+            "hrp: {hrp}\nwitness version: {}\ndata: {}",
+            version.to_u8(),
+            to_hex(&program)
+        ));
+    }
+
+    let (hrp, data) = bech32::decode(input)?;
+    Ok(format!("hrp: {hrp}\ndata: {}", to_hex(&data)))
+}
+
+// matches javascript's encodeURIComponent
+const URL_COMPONENT: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+pub fn url_decode(input: &str, space_as_plus: bool) -> Result<String> {
+    let input = match space_as_plus {
+        true => input.trim().replace('+', " "),
+        false => input.trim().to_string(),
+    };
+
+    percent_encoding::percent_decode_str(&input)
+        .decode_utf8()
+        .map(|decoded| decoded.to_string())
+        .map_err(|err| Error::Input(err.to_string()))
+}
+
+pub fn url_encode(input: &str, space_as_plus: bool) -> String {
+    let encoded = percent_encoding::utf8_percent_encode(input, URL_COMPONENT).to_string();
+
+    match space_as_plus {
+        true => encoded.replace("%20", "+"),
+        false => encoded,
+    }
+}
+
+pub fn unicode_escape(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c.is_ascii() {
+            true => c.to_string(),
+            false => format!("\\u{{{:04x}}}", c as u32),
+        })
+        .collect()
+}
+
+// parses one `XXXX` or `{...}` following a `\u`, returning the code and how many bytes it took,
+// none unless it's well formed so `C:\users` passes through untouched
+fn unicode_code_unit(rest: &str) -> Option<(u32, usize)> {
+    let is_hex = |digits: &str| digits.chars().all(|c| c.is_ascii_hexdigit());
+
+    let (digits, length) = match rest.strip_prefix('{') {
+        Some(braced) => {
+            let digits = &braced[..braced.find('}')?];
+            (digits, digits.len() + 2)
+        }
+        None => (rest.get(..4)?, 4),
+    };
+
+    match !digits.is_empty() && digits.len() <= 6 && is_hex(digits) {
+        true => Some((u32::from_str_radix(digits, 16).ok()?, length)),
+        false => None,
+    }
+}
+
+// handles both `\uXXXX` (with utf16 surrogate pairs, as in json) and `\u{...}`, any other
+// backslash is left alone
+pub fn unicode_unescape(input: &str) -> Result<String> {
+    let mut output = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("\\u") {
+        output.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let Some((mut code, length)) = unicode_code_unit(rest) else {
+            output.push_str("\\u");
+            continue;
+        };
+        rest = &rest[length..];
+
+        if (0xDC00..0xE000).contains(&code) {
+            return Err(Error::Input(format!("unpaired surrogate \\u{code:04x}")));
+        }
+
+        // a high surrogate must be followed by an escaped low surrogate
+        if (0xD800..0xDC00).contains(&code) {
+            let low = rest
+                .strip_prefix("\\u")
+                .and_then(unicode_code_unit)
+                .filter(|(low, _)| (0xDC00..0xE000).contains(low));
+
+            let Some((low, length)) = low else {
+                return Err(Error::Input(format!("unpaired surrogate \\u{code:04x}")));
+            };
+            rest = &rest[length + 2..];
+
+            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+        }
+
+        output.push(char::from_u32(code).ok_or_else(|| {
+            Error::Input(format!("\\u{{{code:x}}} is not a valid unicode character"))
+        })?);
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+const HTML_ENTITIES: [(&str, char); 31] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("bull", '•'),
+    ("middot", '·'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("micro", 'µ'),
+    ("para", '¶'),
+    ("sect", '§'),
+    ("cent", '¢'),
+    ("pound", '£'),
+    ("yen", '¥'),
+    ("euro", '€'),
+];
+
+pub fn html_encode(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&#39;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn html_entity(entity: &str) -> Option<char> {
+    match entity.strip_prefix('#') {
+        Some(code) => match code.strip_prefix(['x', 'X']) {
+            Some(hex) => char::from_u32(u32::from_str_radix(hex, 16).ok()?),
+            None => char::from_u32(code.parse().ok()?),
+        },
+        None => HTML_ENTITIES
+            .iter()
+            .find(|(name, _)| *name == entity)
+            .map(|(_, c)| *c),
+    }
+}
+
+// unknown or malformed entities are left as they are, like a browser would
+pub fn html_decode(input: &str) -> String {
+    let mut output = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest
+            .find(';')
+            .and_then(|end| Some((html_entity(&rest[1..end])?, end)));
+
+        match decoded {
+            Some((c, end)) => {
+                output.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_round_trips_non_ascii() {
+        assert_eq!(binary_decode(&binary_encode("café")).unwrap(), "café");
+    }
+
+    #[test]
+    fn binary_decode_rejects_bad_chunks() {
+        assert!(binary_decode("01100001 2").is_err());
+    }
+
+    #[test]
+    fn roman_round_trips() {
+        for n in 1..=3999 {
+            assert_eq!(from_roman(&to_roman(n).unwrap()).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn roman_rejects_out_of_range_and_non_canonical() {
+        assert!(to_roman(0).is_err());
+        assert!(to_roman(4000).is_err());
+        assert!(from_roman("IIII").is_err());
+        assert!(from_roman("VX").is_err());
+    }
+
+    #[test]
+    fn unicode_unescape_leaves_other_backslashes_alone() {
+        assert_eq!(unicode_unescape(r"C:\users\me").unwrap(), r"C:\users\me");
+        assert_eq!(unicode_unescape(r"\uzz12 \u12").unwrap(), r"\uzz12 \u12");
+        assert_eq!(
+            unicode_unescape(r"caf\u00e9 \ud83d\ude00").unwrap(),
+            "café 😀"
+        );
+    }
+}
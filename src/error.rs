@@ -0,0 +1,67 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to decode base32: {0}")]
+    Base32(#[from] data_encoding::DecodeError),
+
+    #[error("Unable to decode base58: {0}")]
+    Base58(#[from] bs58::decode::Error),
+
+    #[error("Unable to decode base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("Unable to decode bech32: {0}")]
+    Bech32(#[from] bech32::DecodeError),
+
+    #[error("Unable to access clipboard: {0}")]
+    Clipboard(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("Unable to parse input: {0}")]
+    Input(String),
+
+    #[error("Unable to handle csv: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Unable to parse json: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unable to query json: {0}")]
+    JsonQuery(String),
+
+    #[error("Network request failed: {0}")]
+    Network(#[from] ureq::Error),
+
+    #[error("Unable to parse number: {0}")]
+    ParseInt(#[from] std::num::ParseIntError),
+
+    #[error("Unable to create qr code: {0}")]
+    Qr(#[from] qrcode::types::QrError),
+
+    #[error("Unable to compile regex: {0}")]
+    Regex(#[from] regex::Error),
+
+    #[error("Unable to handle time: {0}")]
+    Time(#[from] jiff::Error),
+
+    #[error("Unable to parse toml: {0}")]
+    TomlParse(#[from] toml::de::Error),
+
+    #[error("Unable to convert to toml: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
+
+    #[error("Unable to parse url: {0}")]
+    Url(#[from] url::ParseError),
+
+    #[error("Unable to parse uuid: {0}")]
+    Uuid(#[from] uuid::Error),
+
+    #[error("Output is not valid utf8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+
+    #[error("Unable to parse yaml: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,280 @@
+use crate::{Error, Result, base32_decode_bytes, hmac_digest, now};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+pub const DEFAULT_PASSWORD_LENGTH: usize = 32;
+
+const PASSWORD_SYMBOLS: &str = "!#$%&()*+,-./:;<=>?@[]^_{|}~";
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub symbols: bool,
+    pub digits: bool,
+    pub uppercase: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            symbols: false,
+            digits: true,
+            uppercase: true,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    fn alphabet(&self) -> Vec<char> {
+        let mut alphabet = ('a'..='z').collect::<Vec<_>>();
+
+        if self.uppercase {
+            alphabet.extend('A'..='Z');
+        }
+
+        if self.digits {
+            alphabet.extend('0'..='9');
+        }
+
+        if self.symbols {
+            alphabet.extend(PASSWORD_SYMBOLS.chars());
+        }
+
+        alphabet
+    }
+}
+
+pub fn password_entropy(length: usize, policy: &PasswordPolicy) -> f64 {
+    length as f64 * (policy.alphabet().len() as f64).log2()
+}
+
+pub fn gen_password(length: usize, policy: &PasswordPolicy) -> String {
+    let alphabet = policy.alphabet();
+    let mut rng = rand::rng();
+
+    (0..length)
+        .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+        .collect()
+}
+
+// https://www.eff.org/dice, one "<dice roll>\t<word>" per line
+const EFF_LARGE_WORDLIST: &str = include_str!("eff_large_wordlist.txt");
+
+pub fn gen_passphrase(words: usize, separator: &str) -> String {
+    let wordlist = EFF_LARGE_WORDLIST
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .collect::<Vec<_>>();
+    let mut rng = rand::rng();
+
+    (0..words)
+        .filter_map(|_| wordlist.choose(&mut rng))
+        .copied()
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// all the same digit, or counting straight up or down like 1234 and 9876
+fn is_weak_pin(digits: &[u32]) -> bool {
+    let steps = digits
+        .windows(2)
+        .map(|pair| pair[1] as i32 - pair[0] as i32)
+        .collect::<Vec<_>>();
+
+    !steps.is_empty()
+        && steps
+            .iter()
+            .all(|step| *step == steps[0] && step.abs() <= 1)
+}
+
+pub fn gen_pin(length: usize, reject_weak: bool) -> String {
+    let mut rng = rand::rng();
+
+    loop {
+        let digits = (0..length)
+            .map(|_| rng.random_range(0..10))
+            .collect::<Vec<u32>>();
+
+        if !(reject_weak && is_weak_pin(&digits)) {
+            return digits.iter().map(|digit| digit.to_string()).collect();
+        }
+    }
+}
+
+// aa:bb:cc, aa-bb-cc or aabbcc
+fn parse_oui(input: &str) -> Result<Vec<u8>> {
+    let hex = input.replace([':', '-'], "");
+
+    match hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        true => Ok((0..6)
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()),
+        false => Err(Error::Input(format!(
+            "{input:?} is not an oui, expected three hex bytes like 02:00:5e"
+        ))),
+    }
+}
+
+// random macs are locally administered unicast, a given oui is used exactly as it is
+pub fn gen_mac(oui: Option<&str>) -> Result<String> {
+    let mut rng = rand::rng();
+
+    let mut bytes = match oui {
+        Some(oui) => parse_oui(oui)?,
+        None => {
+            let first = rng.random::<u8>() & 0b1111_1110 | 0b0000_0010;
+            vec![first, rng.random(), rng.random()]
+        }
+    };
+    bytes.extend([rng.random::<u8>(), rng.random(), rng.random()]);
+
+    Ok(bytes
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+pub const LOREM_UNITS: [&str; 3] = ["words", "sentences", "paragraphs"];
+
+const LOREM: &str = "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod \
+    tempor incididunt ut labore et dolore magna aliqua enim ad minim veniam quis nostrud \
+    exercitation ullamco laboris nisi aliquip ex ea commodo consequat duis aute irure in \
+    reprehenderit voluptate velit esse cillum eu fugiat nulla pariatur excepteur sint \
+    occaecat cupidatat non proident sunt culpa qui officia deserunt mollit anim id est \
+    laborum integer";
+
+// always opens with the familiar "lorem ipsum dolor sit amet" so it reads as placeholder text
+fn lorem_words(amount: usize, rng: &mut impl Rng) -> Vec<&'static str> {
+    let words = LOREM.split_whitespace().collect::<Vec<_>>();
+
+    words[..5]
+        .iter()
+        .copied()
+        .chain(std::iter::repeat_with(|| *words.choose(rng).unwrap()))
+        .take(amount)
+        .collect()
+}
+
+fn lorem_sentence(words: Vec<&str>) -> String {
+    let sentence = words.join(" ");
+    let mut chars = sentence.chars();
+
+    match chars.next() {
+        Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+fn lorem_sentences(amount: usize, rng: &mut impl Rng) -> Vec<String> {
+    let lengths = (0..amount)
+        .map(|_| rng.random_range(6..=14))
+        .collect::<Vec<_>>();
+    let words = lorem_words(lengths.iter().sum(), rng);
+
+    lengths
+        .iter()
+        .scan(0, |start, length| {
+            let sentence = lorem_sentence(words[*start..*start + length].to_vec());
+            *start += length;
+            Some(sentence)
+        })
+        .collect()
+}
+
+pub fn gen_lorem(amount: usize, unit: &str) -> String {
+    let mut rng = rand::rng();
+
+    match unit {
+        "words" => lorem_words(amount, &mut rng).join(" "),
+        "sentences" => lorem_sentences(amount, &mut rng).join(" "),
+        _ => {
+            let lengths = (0..amount)
+                .map(|_| rng.random_range(4..=7))
+                .collect::<Vec<_>>();
+            let sentences = lorem_sentences(lengths.iter().sum(), &mut rng);
+
+            lengths
+                .iter()
+                .scan(0, |start, length| {
+                    let paragraph = sentences[*start..*start + length].join(" ");
+                    *start += length;
+                    Some(paragraph)
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        }
+    }
+}
+
+pub const TOTP_ALGORITHMS: [&str; 3] = ["sha1", "sha256", "sha512"];
+
+// rfc 6238, returns the code and the seconds left until it rotates
+pub fn gen_totp(secret: &str, digits: u32, period: u64, algorithm: &str) -> Result<(String, u64)> {
+    let key = base32_decode_bytes(secret)?;
+    let seconds = now().timestamp().as_second() as u64;
+    let counter = seconds / period;
+
+    // dynamic truncation from rfc 4226, the low nibble of the last byte picks an offset
+    let hash = hmac_digest(&key, &counter.to_be_bytes(), algorithm)?;
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let truncated = u32::from_be_bytes(hash[offset..offset + 4].try_into().unwrap()) & 0x7fff_ffff;
+    let code = truncated as u64 % 10u64.pow(digits);
+
+    Ok((
+        format!("{code:0width$}", width = digits as usize),
+        period - seconds % period,
+    ))
+}
+
+pub fn gen_uuid4() -> String {
+    uuid::Uuid::new_v4().as_hyphenated().to_string()
+}
+
+pub fn gen_uuid5(input: &str, namespace: &str) -> Result<String> {
+    let namespace = match namespace.to_lowercase().as_str() {
+        "dns" => uuid::Uuid::NAMESPACE_DNS,
+        "url" => uuid::Uuid::NAMESPACE_URL,
+        "oid" => uuid::Uuid::NAMESPACE_OID,
+        "x500" => uuid::Uuid::NAMESPACE_X500,
+        namespace => uuid::Uuid::parse_str(namespace)?,
+    };
+
+    Ok(uuid::Uuid::new_v5(&namespace, input.trim().as_bytes())
+        .as_hyphenated()
+        .to_string())
+}
+
+pub fn inspect_uuid(input: &str) -> Result<String> {
+    let uuid = uuid::Uuid::parse_str(input.trim())?;
+
+    let mut lines = vec![
+        format!("uuid: {}", uuid.as_hyphenated()),
+        match uuid.get_version() {
+            Some(version) => format!("version: {} ({version:?})", uuid.get_version_num()),
+            None => format!("version: {} (unknown)", uuid.get_version_num()),
+        },
+        format!("variant: {:?}", uuid.get_variant()),
+    ];
+
+    // only v1, v6 and v7 carry a timestamp
+    if let Some(timestamp) = uuid.get_timestamp() {
+        let (seconds, nanoseconds) = timestamp.to_unix();
+        let timestamp = jiff::Timestamp::new(seconds as _, nanoseconds as _)?;
+        lines.push(format!("timestamp: {timestamp}"));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+pub fn gen_uuid7() -> String {
+    let timestamp = now().timestamp();
+
+    uuid::Uuid::new_v7(uuid::Timestamp::from_unix(
+        uuid::NoContext,
+        timestamp.as_second() as _,
+        timestamp.subsec_nanosecond() as _,
+    ))
+    .as_hyphenated()
+    .to_string()
+}
@@ -0,0 +1,128 @@
+use crate::{Error, Result};
+use base64::Engine;
+use hmac::Mac;
+use sha2::Digest;
+use sha2::digest::core_api::BlockSizeUser;
+
+pub const HMAC_ALGORITHMS: [&str; 4] = ["md5", "sha1", "sha256", "sha512"];
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn digest_hex<D: Digest>(input: &str) -> String {
+    to_hex(&D::digest(input.as_bytes()))
+}
+
+fn hmac_bytes<D: Digest + BlockSizeUser>(key: &[u8], input: &[u8]) -> Vec<u8> {
+    // SimpleHmac accepts keys of any length, so this can't fail
+    let mut mac = hmac::SimpleHmac::<D>::new_from_slice(key).unwrap();
+    mac.update(input);
+
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub(crate) fn hmac_digest(key: &[u8], input: &[u8], algorithm: &str) -> Result<Vec<u8>> {
+    match algorithm {
+        "md5" => Ok(hmac_bytes::<md5::Md5>(key, input)),
+        "sha1" => Ok(hmac_bytes::<sha1::Sha1>(key, input)),
+        "sha256" => Ok(hmac_bytes::<sha2::Sha256>(key, input)),
+        "sha512" => Ok(hmac_bytes::<sha2::Sha512>(key, input)),
+        algorithm => Err(Error::Input(format!(
+            "{algorithm:?} is not a supported algorithm, expected one of {}",
+            HMAC_ALGORITHMS.join(", ")
+        ))),
+    }
+}
+
+pub fn hmac(input: &str, key: &str, algorithm: &str) -> Result<String> {
+    Ok(to_hex(&hmac_digest(
+        key.as_bytes(),
+        input.as_bytes(),
+        algorithm,
+    )?))
+}
+
+// like the other hashes this covers the raw utf8 bytes of the input, not a decoded form of it
+pub fn crc32(input: &str, decimal: bool) -> String {
+    let checksum = crc32fast::hash(input.as_bytes());
+
+    match decimal {
+        true => checksum.to_string(),
+        false => format!("{checksum:08x}"),
+    }
+}
+
+pub fn md5(input: &str) -> String {
+    digest_hex::<md5::Md5>(input)
+}
+
+pub fn sha1(input: &str) -> String {
+    digest_hex::<sha1::Sha1>(input)
+}
+
+pub fn sha256(input: &str) -> String {
+    digest_hex::<sha2::Sha256>(input)
+}
+
+pub const SSH_KEY_TYPES: [&str; 6] = [
+    "ssh-ed25519",
+    "ssh-rsa",
+    "ecdsa-sha2-nistp256",
+    "ecdsa-sha2-nistp384",
+    "ecdsa-sha2-nistp521",
+    "sk-ssh-ed25519@openssh.com",
+];
+
+// the key blob starts with its own type as a length prefixed string
+fn ssh_blob_type(blob: &[u8]) -> Option<&str> {
+    let length = u32::from_be_bytes(blob.get(..4)?.try_into().ok()?) as usize;
+    std::str::from_utf8(blob.get(4..4 + length)?).ok()
+}
+
+fn ssh_key_fingerprint(line: &str) -> Result<String> {
+    let invalid = |reason: &str| Error::Input(format!("not a valid ssh public key, {reason}"));
+
+    // authorized_keys lines can have options before the key type
+    let mut fields = line.split_whitespace();
+    let key_type = fields
+        .find(|field| SSH_KEY_TYPES.contains(field))
+        .ok_or_else(|| {
+            invalid(&format!(
+                "expected it to start with one of {}",
+                SSH_KEY_TYPES.join(", ")
+            ))
+        })?;
+    let body = fields
+        .next()
+        .ok_or_else(|| invalid(&format!("nothing follows {key_type}")))?;
+
+    let blob = base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .map_err(|err| invalid(&format!("the key body isn't base64, {err}")))?;
+
+    if ssh_blob_type(&blob) != Some(key_type) {
+        return Err(invalid(&format!("the key body doesn't match {key_type}")));
+    }
+
+    let digest = sha2::Sha256::digest(&blob);
+    Ok(format!(
+        "SHA256:{}",
+        base64::engine::general_purpose::STANDARD_NO_PAD.encode(digest)
+    ))
+}
+
+// one fingerprint per key, so a whole authorized_keys file can be pasted, like `ssh-keygen -l`
+pub fn ssh_fingerprint(input: &str) -> Result<String> {
+    let fingerprints = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ssh_key_fingerprint)
+        .collect::<Result<Vec<_>>>()?;
+
+    match fingerprints.is_empty() {
+        true => Err(Error::Input("expected an ssh public key".to_string())),
+        false => Ok(fingerprints.join("\n")),
+    }
+}
@@ -0,0 +1,666 @@
+use crate::{Error, Result, base64_decode};
+use std::cmp::Ordering;
+
+fn parse_json(input: &str) -> Result<serde_json::Value> {
+    Ok(serde_json::from_str::<serde_json::Value>(input)?)
+}
+
+pub fn format_json(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+pub fn minify_json(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    Ok(serde_json::to_string(&json)?)
+}
+
+#[derive(Debug)]
+enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl std::fmt::Display for JsonPathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonPathSegment::Key(key) => write!(f, ".{key}"),
+            JsonPathSegment::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
+// supports `.foo.bar[0]` and `["some key"]`, the leading dot is optional
+fn parse_json_path(path: &str) -> Result<Vec<JsonPathSegment>> {
+    let path = path.trim();
+    let path = match path.starts_with(['.', '[']) {
+        true => path.to_string(),
+        false => format!(".{path}"),
+    };
+
+    let mut segments = Vec::new();
+    let mut chars = path.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                    key.push(c);
+                }
+
+                if !key.is_empty() {
+                    segments.push(JsonPathSegment::Key(key));
+                }
+            }
+            '[' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err(Error::JsonQuery(format!("unclosed '[' in {path:?}"))),
+                    }
+                }
+
+                let inner = inner.trim();
+                let segment =
+                    match inner
+                        .strip_prefix('"')
+                        .and_then(|key| key.strip_suffix('"'))
+                    {
+                        Some(key) => JsonPathSegment::Key(key.to_string()),
+                        None => JsonPathSegment::Index(inner.parse().map_err(|_| {
+                            Error::JsonQuery(format!("invalid array index {inner:?}"))
+                        })?),
+                    };
+                segments.push(segment);
+            }
+            c => {
+                return Err(Error::JsonQuery(format!(
+                    "unexpected {c:?} in {path:?}, expected '.' or '['"
+                )));
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+pub fn query_json(input: &str, path: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    let mut value = &json;
+    let mut walked = String::new();
+
+    for segment in parse_json_path(path)? {
+        value = match (&segment, value) {
+            (JsonPathSegment::Key(key), serde_json::Value::Object(map)) => map
+                .get(key)
+                .ok_or_else(|| Error::JsonQuery(format!("key {key:?} not found at '{walked}'")))?,
+            (JsonPathSegment::Index(index), serde_json::Value::Array(items)) => {
+                items.get(*index).ok_or_else(|| {
+                    Error::JsonQuery(format!(
+                        "index {index} out of range at '{walked}', length is {}",
+                        items.len()
+                    ))
+                })?
+            }
+            (segment, _) => {
+                return Err(Error::JsonQuery(format!(
+                    "cannot apply '{segment}' at '{walked}', value is not an {}",
+                    match segment {
+                        JsonPathSegment::Key(_) => "object",
+                        JsonPathSegment::Index(_) => "array",
+                    }
+                )));
+            }
+        };
+
+        walked.push_str(&segment.to_string());
+    }
+
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+// keys use the same `.key`, `["some key"]` and `[0]` notation as json-query, empty objects and
+// arrays are kept as leaves so unflattening gives back exactly the same document
+fn flatten(
+    value: &serde_json::Value,
+    path: String,
+    output: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<()> {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                if key.contains(']') {
+                    return Err(Error::Input(format!("key {key:?} can't be flattened")));
+                }
+
+                let path = match (key.is_empty() || key.contains(['.', '[']), path.is_empty()) {
+                    (true, _) => format!("{path}[\"{key}\"]"),
+                    (false, true) => key.clone(),
+                    (false, false) => format!("{path}.{key}"),
+                };
+                flatten(value, path, output)?;
+            }
+        }
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(value, format!("{path}[{i}]"), output)?;
+            }
+        }
+        value => {
+            output.insert(path, value.clone());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn flatten_json(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+
+    if !json.is_object() && !json.is_array() {
+        return Err(Error::Input("expected a json object or array".to_string()));
+    }
+
+    let mut output = serde_json::Map::new();
+    flatten(&json, String::new(), &mut output)?;
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+// `keys` is how many keys the flat object has, an index far past that can't come from
+// flattening and would only allocate a huge array of nulls
+fn unflatten(
+    target: &mut serde_json::Value,
+    segments: &[JsonPathSegment],
+    value: serde_json::Value,
+    key: &str,
+    keys: usize,
+) -> Result<()> {
+    let conflict = || Error::Input(format!("key {key:?} conflicts with another key"));
+
+    match segments.split_first() {
+        None if target.is_null() => *target = value,
+        None => return Err(conflict()),
+        Some((JsonPathSegment::Key(name), rest)) => {
+            if target.is_null() {
+                *target = serde_json::Value::Object(serde_json::Map::new());
+            }
+
+            let map = target.as_object_mut().ok_or_else(conflict)?;
+            let child = map.entry(name).or_insert(serde_json::Value::Null);
+            unflatten(child, rest, value, key, keys)?;
+        }
+        Some((JsonPathSegment::Index(index), rest)) => {
+            if target.is_null() {
+                *target = serde_json::Value::Array(Vec::new());
+            }
+
+            // indexes are normally in order, any gaps are filled with nulls
+            let items = target.as_array_mut().ok_or_else(conflict)?;
+            if *index > items.len() + keys {
+                return Err(Error::Input(format!(
+                    "key {key:?} has index {index}, far more than the {keys} keys given"
+                )));
+            }
+            if items.len() <= *index {
+                items.resize(index + 1, serde_json::Value::Null);
+            }
+            unflatten(&mut items[*index], rest, value, key, keys)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn unflatten_json(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    let map = json
+        .as_object()
+        .ok_or_else(|| Error::Input("expected a flat json object".to_string()))?;
+
+    let mut output = serde_json::Value::Null;
+    for (key, value) in map {
+        unflatten(
+            &mut output,
+            &parse_json_path(key)?,
+            value.clone(),
+            key,
+            map.len(),
+        )?;
+    }
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+// repeated keys like `scope=a&scope=b` are collected into an array
+pub(crate) fn query_pairs_to_json<'a>(
+    pairs: impl Iterator<Item = (std::borrow::Cow<'a, str>, std::borrow::Cow<'a, str>)>,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut output = serde_json::Map::new();
+
+    for (key, value) in pairs {
+        let value = serde_json::Value::String(value.into_owned());
+        match output.get_mut(key.as_ref()) {
+            Some(serde_json::Value::Array(values)) => values.push(value),
+            Some(existing) => *existing = serde_json::Value::Array(vec![existing.take(), value]),
+            None => {
+                output.insert(key.into_owned(), value);
+            }
+        }
+    }
+
+    output
+}
+
+// takes a bare `a=1&b=2` or anything with a `?` in front of it, like a whole url
+pub fn querystring_to_json(input: &str) -> Result<String> {
+    let input = input.trim();
+    let query = input.split_once('?').map_or(input, |(_, query)| query);
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+
+    let json = query_pairs_to_json(url::form_urlencoded::parse(query.as_bytes()));
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+// arrays become repeated keys, nested objects have no querystring equivalent
+pub fn json_to_querystring(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    let map = json
+        .as_object()
+        .ok_or_else(|| Error::Input("expected a json object".to_string()))?;
+
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in map {
+        let values = match value {
+            serde_json::Value::Array(items) => items.iter().collect(),
+            value => vec![value],
+        };
+
+        for value in values {
+            match value {
+                serde_json::Value::Null => query.append_key_only(key),
+                serde_json::Value::String(value) => query.append_pair(key, value),
+                serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {
+                    query.append_pair(key, &value.to_string())
+                }
+                _ => {
+                    return Err(Error::Input(format!(
+                        "{key:?} holds a nested value, which a querystring can't represent"
+                    )));
+                }
+            };
+        }
+    }
+
+    Ok(query.finish())
+}
+
+// double quotes support \n, \t, \" and \\ escapes, single quotes are taken literally and
+// unquoted values end at an inline ` #` comment
+fn parse_env_value(value: &str, line: usize) -> Result<String> {
+    let unterminated =
+        |quote| Error::Input(format!("line {line} has an unterminated {quote} quote"));
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut output = String::new();
+        let mut chars = rest.chars();
+
+        loop {
+            match chars.next().ok_or_else(|| unterminated("double"))? {
+                '"' => return Ok(output),
+                '\\' => match chars.next().ok_or_else(|| unterminated("double"))? {
+                    'n' => output.push('\n'),
+                    't' => output.push('\t'),
+                    c => output.push(c),
+                },
+                c => output.push(c),
+            }
+        }
+    }
+
+    if let Some(rest) = value.strip_prefix('\'') {
+        let (value, _) = rest
+            .split_once('\'')
+            .ok_or_else(|| unterminated("single"))?;
+        return Ok(value.to_string());
+    }
+
+    let value = value.split_once(" #").map_or(value, |(value, _)| value);
+    Ok(value.trim().to_string())
+}
+
+pub fn env_to_json(input: &str) -> Result<String> {
+    let mut output = serde_json::Map::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| Error::Input(format!("line {} is not KEY=value", i + 1)))?;
+        let value = parse_env_value(value.trim_start(), i + 1)?;
+
+        output.insert(key.trim().to_string(), serde_json::Value::String(value));
+    }
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+fn format_env_value(value: &str) -> String {
+    let plain = !value.is_empty()
+        && !value.contains(|c: char| c.is_whitespace() || "\"'#$\\=`".contains(c));
+
+    match plain {
+        true => value.to_string(),
+        false => format!(
+            "\"{}\"",
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\t', "\\t")
+        ),
+    }
+}
+
+pub fn json_to_env(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    let map = json
+        .as_object()
+        .ok_or_else(|| Error::Input("expected a json object".to_string()))?;
+
+    map.iter()
+        .map(|(key, value)| {
+            if key.is_empty() || key.contains(|c: char| c.is_whitespace() || c == '=') {
+                return Err(Error::Input(format!(
+                    "{key:?} is not a valid variable name"
+                )));
+            }
+
+            let value = match value {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(value) => format_env_value(value),
+                serde_json::Value::Bool(_) | serde_json::Value::Number(_) => value.to_string(),
+                _ => {
+                    return Err(Error::Input(format!(
+                        "{key:?} holds a nested value, which an env file can't represent"
+                    )));
+                }
+            };
+
+            Ok(format!("{key}={value}"))
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|lines| lines.join("\n"))
+}
+
+fn lookup_path<'a>(
+    value: &'a serde_json::Value,
+    segments: &[JsonPathSegment],
+) -> Option<&'a serde_json::Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match (segment, value) {
+            (JsonPathSegment::Key(key), serde_json::Value::Object(map)) => map.get(key),
+            (JsonPathSegment::Index(index), serde_json::Value::Array(items)) => items.get(*index),
+            _ => None,
+        })
+}
+
+// numbers before strings before anything else, missing keys and nulls always last
+fn compare_sort_values(a: Option<&serde_json::Value>, b: Option<&serde_json::Value>) -> Ordering {
+    let rank = |value: Option<&serde_json::Value>| match value {
+        Some(serde_json::Value::Number(_)) => 0,
+        Some(serde_json::Value::String(_)) => 1,
+        Some(serde_json::Value::Bool(_)) => 2,
+        Some(serde_json::Value::Array(_) | serde_json::Value::Object(_)) => 3,
+        Some(serde_json::Value::Null) | None => 4,
+    };
+
+    match (a, b) {
+        (Some(serde_json::Value::Number(a)), Some(serde_json::Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(serde_json::Value::String(a)), Some(serde_json::Value::String(b))) => a.cmp(b),
+        (Some(serde_json::Value::Bool(a)), Some(serde_json::Value::Bool(b))) => a.cmp(b),
+        (a, b) => rank(a).cmp(&rank(b)),
+    }
+}
+
+// `by` uses the same notation as json-query, so nested keys work too
+pub fn sort_json_array(input: &str, by: &str) -> Result<String> {
+    let serde_json::Value::Array(mut items) = parse_json(input)? else {
+        return Err(Error::Input("expected a json array".to_string()));
+    };
+
+    let segments = parse_json_path(by)?;
+
+    // sort_by is stable, so items with equal keys keep their order
+    items.sort_by(|a, b| compare_sort_values(lookup_path(a, &segments), lookup_path(b, &segments)));
+
+    Ok(serde_json::to_string_pretty(&items)?)
+}
+
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map
+                .into_iter()
+                .map(|(key, value)| (key, sort_keys(value)))
+                .collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            serde_json::Value::Object(entries.into_iter().collect())
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+        }
+        value => value,
+    }
+}
+
+pub fn sort_json_keys(input: &str) -> Result<String> {
+    let json = sort_keys(parse_json(input)?);
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+pub fn json_string_escape(input: &str) -> Result<String> {
+    Ok(serde_json::to_string(input)?)
+}
+
+// the surrounding quotes are optional, copying just the inside of a string literal is common
+pub fn json_string_unescape(input: &str) -> Result<String> {
+    let input = input.trim();
+    let literal = match input.len() >= 2 && input.starts_with('"') && input.ends_with('"') {
+        true => input.to_string(),
+        false => format!("\"{input}\""),
+    };
+
+    Ok(serde_json::from_str::<String>(&literal)?)
+}
+
+// toml documents are always a table at the top, and toml has no null
+pub fn json_to_toml(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+
+    if !json.is_object() {
+        return Err(Error::Input(
+            "toml can only represent an object at the top level".to_string(),
+        ));
+    }
+
+    let mut leaves = serde_json::Map::new();
+    if flatten(&json, String::new(), &mut leaves).is_ok()
+        && let Some((key, _)) = leaves.iter().find(|(_, value)| value.is_null())
+    {
+        return Err(Error::Input(format!(
+            "{key} is null, which toml can't represent"
+        )));
+    }
+
+    Ok(toml::to_string_pretty(&json)?)
+}
+
+// datetimes have no json equivalent, so they become strings
+fn toml_to_value(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(string) => serde_json::Value::String(string),
+        toml::Value::Integer(integer) => serde_json::Value::from(integer),
+        toml::Value::Float(float) => serde_json::Value::from(float),
+        toml::Value::Boolean(boolean) => serde_json::Value::Bool(boolean),
+        toml::Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        toml::Value::Array(items) => items.into_iter().map(toml_to_value).collect(),
+        toml::Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| (key, toml_to_value(value)))
+            .collect(),
+    }
+}
+
+pub fn toml_to_json(input: &str) -> Result<String> {
+    let table = toml::from_str::<toml::Table>(input)?;
+    let json = toml_to_value(toml::Value::Table(table));
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+pub fn json_to_yaml(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    Ok(serde_yaml::to_string(&json)?)
+}
+
+pub fn yaml_to_json(input: &str) -> Result<String> {
+    let json = serde_yaml::from_str::<serde_json::Value>(input)?;
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+// only decodes, the signature is never checked
+pub fn decode_jwt(input: &str) -> Result<String> {
+    let input = input.trim();
+    let input = input.strip_prefix("Bearer ").unwrap_or(input);
+    let segments = input.split('.').collect::<Vec<_>>();
+
+    let [header, payload, _signature] = segments[..] else {
+        return Err(Error::Input(format!(
+            "a jwt has 3 segments separated by '.', found {}",
+            segments.len()
+        )));
+    };
+
+    let decode = |name: &str, segment: &str| -> Result<serde_json::Value> {
+        let json = base64_decode(segment, true).map_err(|err| match err {
+            Error::Base64(err) => Error::Input(format!("the jwt {name} is not base64url, {err}")),
+            err => err,
+        })?;
+
+        serde_json::from_str(&json)
+            .map_err(|err| Error::Input(format!("the jwt {name} is not valid json, {err}")))
+    };
+
+    let decoded = serde_json::json!({
+        "header": decode("header", header)?,
+        "payload": decode("payload", payload)?,
+    });
+
+    Ok(serde_json::to_string_pretty(&decoded)?)
+}
+
+// the first row is the header, every value stays a string since csv has no types
+pub fn csv_to_json(input: &str) -> Result<String> {
+    let mut reader = csv::Reader::from_reader(input.as_bytes());
+    let headers = reader.headers()?.clone();
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            Ok(headers
+                .iter()
+                .zip(record?.iter())
+                .map(|(key, value)| (key.to_string(), value.into()))
+                .collect::<serde_json::Map<_, _>>())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(serde_json::to_string_pretty(&rows)?)
+}
+
+fn csv_field(value: &serde_json::Value) -> Result<String> {
+    match value {
+        serde_json::Value::Null => Ok(String::new()),
+        serde_json::Value::String(value) => Ok(value.clone()),
+        // nested values are written as json so nothing is lost
+        value => Ok(serde_json::to_string(value)?),
+    }
+}
+
+// the keys of the first object become the header, every other object must have the same keys
+pub fn json_to_csv(input: &str) -> Result<String> {
+    let json = parse_json(input)?;
+    let rows = json
+        .as_array()
+        .ok_or_else(|| Error::Input("expected a json array of objects".to_string()))?
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.as_object()
+                .ok_or_else(|| Error::Input(format!("item {i} is not an object")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some(headers) = rows
+        .first()
+        .map(|row| row.keys().cloned().collect::<Vec<_>>())
+    else {
+        return Ok(String::new());
+    };
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&headers)?;
+
+    for (i, row) in rows.iter().enumerate() {
+        if let Some(key) = row.keys().find(|key| !headers.contains(key)) {
+            return Err(Error::Input(format!(
+                "item {i} has an unexpected key {key:?}"
+            )));
+        }
+
+        let fields = headers
+            .iter()
+            .map(|key| match row.get(key) {
+                Some(value) => csv_field(value),
+                None => Err(Error::Input(format!("item {i} is missing the key {key:?}"))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        writer.write_record(fields)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| Error::Io(err.into_error()))?;
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unflatten_builds_arrays() {
+        let json = unflatten_json(r#"{"a[0]": 1, "a[2].b": 2}"#).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({"a": [1, null, {"b": 2}]})
+        );
+    }
+
+    #[test]
+    fn unflatten_rejects_huge_indexes() {
+        assert!(unflatten_json(r#"{"a[99999999999]": 1}"#).is_err());
+    }
+}
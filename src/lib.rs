@@ -0,0 +1,33 @@
+mod checksum;
+mod color;
+mod cron;
+mod encoding;
+mod error;
+mod generate;
+mod hash;
+mod json;
+mod markdown;
+mod net;
+mod qr;
+mod sql;
+mod text;
+mod time;
+mod units;
+mod xml;
+
+pub use checksum::*;
+pub use color::*;
+pub use cron::*;
+pub use encoding::*;
+pub use error::{Error, Result};
+pub use generate::*;
+pub use hash::*;
+pub use json::*;
+pub use markdown::*;
+pub use net::*;
+pub use qr::*;
+pub use sql::*;
+pub use text::*;
+pub use time::*;
+pub use units::*;
+pub use xml::*;
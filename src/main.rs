@@ -1,58 +1,880 @@
-use clap::{Parser, Subcommand};
-use copypasta::ClipboardProvider;
-use rand::Rng;
+mod clipboard;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clipboard::Clipboard;
+use narigama_utils::*;
+use std::io::{IsTerminal, Read};
 use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
-use url::Url;
 
 #[derive(Debug, Parser)]
 pub struct Arguments {
     #[clap(subcommand)]
     command: Command,
+
+    /// Use this instead of the clipboard contents
+    #[clap(global = true)]
+    input: Option<String>,
+
+    /// Read input from stdin and write the result to stdout only
+    #[clap(long, global = true)]
+    stdin: bool,
+
+    /// Print the result without writing it back to the clipboard
+    #[clap(long, global = true)]
+    no_clipboard: bool,
+
+    /// Number of values to generate, one per line
+    #[clap(long, global = true, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    count: u32,
+
+    /// Print the result and where the input came from to stderr, leaving the clipboard alone
+    #[clap(long, global = true)]
+    dry_run: bool,
+
+    /// Log what's going on to stderr
+    #[clap(long, global = true)]
+    verbose: bool,
 }
 
 #[derive(Debug, Subcommand, EnumIter, EnumString, Display)]
 pub enum Command {
+    /// Print an Alfred script filter listing every command
+    #[strum(serialize = "config-alfred")]
+    ConfigAlfred,
+
+    /// Print an espanso config with a trigger for every command
+    #[strum(serialize = "config-espanso")]
     ConfigEspanso,
 
+    /// Print a Raycast script command for every command
+    #[strum(serialize = "config-raycast")]
+    ConfigRaycast,
+
+    /// Convert text to full width characters
+    #[strum(serialize = "aesthetic")]
+    Aesthetic,
+
+    /// Convert a number between bases
+    #[strum(serialize = "base-convert")]
+    BaseConvert {
+        /// Base of the input, detected from a 0x, 0b or 0o prefix otherwise
+        #[clap(long, value_parser = clap::value_parser!(u32).range(2..=36))]
+        from: Option<u32>,
+
+        /// Base to convert to, prints binary, octal, decimal and hex otherwise
+        #[clap(long, value_parser = clap::value_parser!(u32).range(2..=36))]
+        to: Option<u32>,
+    },
+
+    /// Decode base32
+    #[strum(serialize = "base32-decode")]
+    Base32Decode,
+
+    /// Encode as base32
+    #[strum(serialize = "base32-encode")]
+    Base32Encode,
+
+    /// Decode base58
+    #[strum(serialize = "base58-decode")]
+    Base58Decode {
+        /// Verify and strip a base58check checksum
+        #[clap(long)]
+        check: bool,
+    },
+
+    /// Encode as base58
+    #[strum(serialize = "base58-encode")]
+    Base58Encode {
+        /// Append a base58check checksum
+        #[clap(long)]
+        check: bool,
+    },
+
+    /// Decode base64
+    #[strum(serialize = "base64-decode")]
+    Base64Decode {
+        #[clap(long)]
+        url_safe: bool,
+    },
+
+    /// Encode as base64
+    #[strum(serialize = "base64-encode")]
+    Base64Encode {
+        #[clap(long)]
+        url_safe: bool,
+    },
+
+    /// Decode bech32 into its prefix and hex data
+    #[strum(serialize = "bech32-decode")]
+    Bech32Decode,
+
+    /// Encode hex data as bech32
+    #[strum(serialize = "bech32-encode")]
+    Bech32Encode {
+        /// Human readable prefix, like bc or lnbc
+        #[clap(long)]
+        hrp: String,
+
+        /// Use the bech32m checksum instead
+        #[clap(long)]
+        bech32m: bool,
+    },
+
+    /// Decode space separated binary bytes
     #[strum(serialize = "binary-decode")]
     BinaryDecode,
 
+    /// Encode as space separated binary bytes
     #[strum(serialize = "binary-encode")]
     BinaryEncode,
 
+    /// Shift letters along the alphabet
+    #[strum(serialize = "caesar")]
+    Caesar {
+        /// Letters to shift by, negative to decode
+        #[clap(long, default_value_t = 13, allow_negative_numbers = true)]
+        shift: i32,
+    },
+
+    /// Convert to camelCase
+    #[strum(serialize = "camel-case")]
+    CamelCase,
+
+    /// Capitalize the first letter of the text
+    #[strum(serialize = "capitalize-first")]
+    CapitalizeFirst,
+
+    /// Capitalize the first letter of each line
+    #[strum(serialize = "capitalize-lines")]
+    CapitalizeLines,
+
+    /// Show the codepoint, name and category of each character
+    #[strum(serialize = "char-info")]
+    CharInfo,
+
+    /// Convert a color between hex, rgb and hsl
+    #[strum(serialize = "color")]
+    Color,
+
+    // not a transform, so it's hidden and skipped when iterating commands
+    /// Print shell completions
+    #[clap(hide = true)]
+    #[strum(disabled)]
+    Completions { shell: clap_complete::Shell },
+
+    /// Convert a temperature, length or weight like 100C or 5km to another unit
+    #[strum(serialize = "convert")]
+    Convert {
+        /// Unit to convert to, like F, mi or lb
+        to: String,
+    },
+
+    /// Count lines, words, characters and bytes
+    #[strum(serialize = "count")]
+    Count,
+
+    /// Print the crc32 checksum
+    #[strum(serialize = "crc32")]
+    Crc32 {
+        /// Print the checksum as a decimal number instead of hex
+        #[clap(long)]
+        decimal: bool,
+    },
+
+    /// Describe a cron expression and when it runs next
+    #[strum(serialize = "cron-describe")]
+    CronDescribe,
+
+    /// Convert csv with a header row to a json array
+    #[strum(serialize = "csv-to-json")]
+    CsvToJson,
+
+    /// Wrap as a base64 data uri
+    #[strum(serialize = "data-uri")]
+    DataUri {
+        /// Mime type to label the data with
+        #[clap(long, default_value = "text/plain")]
+        mime: String,
+    },
+
+    /// Unwrap a data uri back into its contents
+    #[strum(serialize = "data-uri-decode")]
+    DataUriDecode,
+
+    /// Print the current date and time
+    #[strum(serialize = "datetime")]
+    Datetime {
+        /// strftime style format, defaults to rfc 3339
+        #[clap(long)]
+        format: Option<String>,
+
+        #[clap(long, default_value = "UTC")]
+        tz: String,
+    },
+
+    /// Show a unified diff between the input and another text
+    #[strum(serialize = "diff")]
+    Diff {
+        /// Text to compare against
+        #[clap(required_unless_present = "file")]
+        other: Option<String>,
+
+        /// Read the text to compare against from a file instead
+        #[clap(long, conflicts_with = "other")]
+        file: Option<std::path::PathBuf>,
+    },
+
+    /// Convert a duration like 1h30m to seconds
+    #[strum(serialize = "duration")]
+    Duration,
+
+    /// Format seconds as a duration like 1h30m
+    #[strum(serialize = "duration-format")]
+    DurationFormat,
+
+    /// Replace emoji with their shortcodes
+    #[strum(serialize = "emoji-decode")]
+    EmojiDecode,
+
+    /// Replace shortcodes like :rocket: with emoji
+    #[strum(serialize = "emoji-encode")]
+    EmojiEncode,
+
+    /// Convert a .env file to json
+    #[strum(serialize = "env-to-json")]
+    EnvToJson,
+
+    /// Pretty print json
     #[strum(serialize = "format-json")]
     FormatJson,
 
+    /// Pretty print sql
+    #[strum(serialize = "format-sql")]
+    FormatSql {
+        /// Uppercase keywords like select and from
+        #[clap(long)]
+        uppercase: bool,
+    },
+
+    /// Pretty print xml
+    #[strum(serialize = "format-xml")]
+    FormatXml,
+
+    /// Compress with gzip and encode as base64
+    #[strum(serialize = "gzip-pack")]
+    GzipPack,
+
+    /// Decode base64 and decompress with gzip
+    #[strum(serialize = "gzip-unpack")]
+    GzipUnpack,
+
+    /// Decode hex
+    #[strum(serialize = "hex-decode")]
+    HexDecode,
+
+    /// Encode as hex
+    #[strum(serialize = "hex-encode")]
+    HexEncode {
+        #[clap(long, default_value = "")]
+        separator: String,
+    },
+
+    /// Show the bytes as a hex dump
+    #[strum(serialize = "hexdump")]
+    Hexdump,
+
+    /// Sign with an hmac
+    #[strum(serialize = "hmac")]
+    Hmac {
+        #[clap(long)]
+        key: String,
+
+        #[clap(long, default_value = "sha256", value_parser = HMAC_ALGORITHMS)]
+        algorithm: String,
+    },
+
+    /// Decode html entities
+    #[strum(serialize = "html-decode")]
+    HtmlDecode,
+
+    /// Escape html special characters as entities
+    #[strum(serialize = "html-encode")]
+    HtmlEncode,
+
+    /// Convert html to markdown
+    #[strum(serialize = "html-to-markdown")]
+    HtmlToMarkdown,
+
+    /// Print the public ip address
     #[strum(serialize = "ip")]
-    Ip,
+    Ip {
+        #[clap(long)]
+        v6: bool,
+
+        /// Report both the v4 and v6 address, whichever are available
+        #[clap(long, conflicts_with = "v6")]
+        both: bool,
+    },
 
+    /// Print the local ip address
+    #[strum(serialize = "ip-local")]
+    IpLocal {
+        /// List every non-loopback address with its interface name
+        #[clap(long)]
+        all: bool,
+    },
+
+    /// Validate an isbn and convert it to isbn-13
+    #[strum(serialize = "isbn")]
+    Isbn,
+
+    /// Flatten nested json into path keys
+    #[strum(serialize = "json-flatten")]
+    JsonFlatten,
+
+    /// Minify json
+    #[strum(serialize = "json-minify")]
+    JsonMinify,
+
+    /// Extract a value from json by path
+    #[strum(serialize = "json-query")]
+    JsonQuery {
+        #[clap(default_value = ".")]
+        path: String,
+    },
+
+    /// Sort a json array by the value of a key
+    #[strum(serialize = "json-sort-array")]
+    JsonSortArray {
+        /// Key to sort by, nested keys use the same path syntax as json-query
+        #[clap(long)]
+        by: String,
+    },
+
+    /// Sort json object keys recursively
+    #[strum(serialize = "json-sort-keys")]
+    JsonSortKeys,
+
+    /// Escape as a json string literal
+    #[strum(serialize = "json-string-escape")]
+    JsonStringEscape,
+
+    /// Unescape a json string literal
+    #[strum(serialize = "json-string-unescape")]
+    JsonStringUnescape,
+
+    /// Convert a json array of objects to csv
+    #[strum(serialize = "json-to-csv")]
+    JsonToCsv,
+
+    /// Convert a json object to a .env file
+    #[strum(serialize = "json-to-env")]
+    JsonToEnv,
+
+    /// Convert a json object to a query string
+    #[strum(serialize = "json-to-querystring")]
+    JsonToQuerystring,
+
+    /// Convert a json object to toml
+    #[strum(serialize = "json-to-toml")]
+    JsonToToml,
+
+    /// Convert json to yaml
+    #[strum(serialize = "json-to-yaml")]
+    JsonToYaml,
+
+    /// Expand flattened path keys into nested json
+    #[strum(serialize = "json-unflatten")]
+    JsonUnflatten,
+
+    /// Decode a jwt's header and payload without verifying it
+    #[strum(serialize = "jwt-decode")]
+    JwtDecode,
+
+    /// Convert to kebab-case
+    #[strum(serialize = "kebab-case")]
+    KebabCase,
+
+    /// Convert to leetspeak
+    #[strum(serialize = "leet")]
+    Leet {
+        /// 1 swaps the most common letters, 3 swaps everything it can
+        #[clap(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=3))]
+        level: u8,
+    },
+
+    /// Remove duplicate lines
+    #[strum(serialize = "lines-dedup")]
+    LinesDedup,
+
+    /// Reverse the order of lines
+    #[strum(serialize = "lines-reverse")]
+    LinesReverse,
+
+    /// Shuffle lines
+    #[strum(serialize = "lines-shuffle")]
+    LinesShuffle,
+
+    /// Sort lines
+    #[strum(serialize = "lines-sort")]
+    LinesSort,
+
+    /// List every command
+    #[strum(serialize = "list")]
+    List,
+
+    /// Generate lorem ipsum text
+    #[strum(serialize = "lorem")]
+    Lorem {
+        #[clap(long, default_value_t = 1)]
+        amount: usize,
+
+        #[clap(long, default_value = "paragraphs", value_parser = LOREM_UNITS)]
+        unit: String,
+    },
+
+    /// Check a number against its luhn check digit
+    #[strum(serialize = "luhn")]
+    Luhn,
+
+    /// Append a luhn check digit
+    #[strum(serialize = "luhn-complete")]
+    LuhnComplete,
+
+    /// Generate a random locally administered mac address
+    #[strum(serialize = "mac")]
+    Mac {
+        /// First three bytes to use instead of random ones
+        #[clap(long)]
+        oui: Option<String>,
+    },
+
+    /// Convert markdown to html
+    #[strum(serialize = "markdown-to-html")]
+    MarkdownToHtml,
+
+    /// Print the md5 hash
+    #[strum(serialize = "md5")]
+    Md5,
+
+    /// Decode morse code
+    #[strum(serialize = "morse-decode")]
+    MorseDecode,
+
+    /// Encode as morse code
+    #[strum(serialize = "morse-encode")]
+    MorseEncode,
+
+    /// Spell out with the nato phonetic alphabet
+    #[strum(serialize = "nato")]
+    Nato,
+
+    /// Strip trailing whitespace and collapse blank lines
+    #[strum(serialize = "normalize-whitespace")]
+    NormalizeWhitespace {
+        /// Keep trailing spaces at the end of each line
+        #[clap(long)]
+        no_strip_trailing: bool,
+
+        /// Keep runs of three or more blank lines instead of collapsing them to one
+        #[clap(long)]
+        no_collapse_blank_lines: bool,
+
+        /// Don't force a single trailing newline
+        #[clap(long)]
+        no_final_newline: bool,
+    },
+
+    /// Group the digits of a number in thousands
+    #[strum(serialize = "number-format")]
+    NumberFormat {
+        #[clap(long, default_value = ",")]
+        separator: String,
+
+        /// Decimal point to use in the output, the input always uses a period
+        #[clap(long, default_value = ".")]
+        decimal: String,
+    },
+
+    /// Convert to PascalCase
+    #[strum(serialize = "pascal-case")]
+    PascalCase,
+
+    /// Generate a random passphrase
+    #[strum(serialize = "passphrase")]
+    Passphrase {
+        #[clap(long, default_value_t = 5)]
+        words: usize,
+
+        #[clap(long, default_value = "-")]
+        separator: String,
+    },
+
+    /// Generate a random password
     #[strum(serialize = "password")]
-    Password,
+    Password {
+        #[clap(long)]
+        symbols: bool,
+
+        #[clap(long)]
+        no_digits: bool,
+
+        #[clap(long)]
+        no_uppercase: bool,
 
+        /// Print the estimated entropy in bits to stderr
+        #[clap(long)]
+        entropy: bool,
+    },
+
+    /// Generate a random pin
+    #[strum(serialize = "pin")]
+    Pin {
+        #[clap(long, default_value_t = 6, value_parser = clap::value_parser!(u32).range(1..))]
+        length: u32,
+
+        /// Reject repeated or sequential pins like 1111 or 1234
+        #[clap(long)]
+        no_weak: bool,
+    },
+
+    /// Convert a punycode domain to unicode
+    #[strum(serialize = "punycode-decode")]
+    PunycodeDecode,
+
+    /// Convert a unicode domain to punycode
+    #[strum(serialize = "punycode-encode")]
+    PunycodeEncode,
+
+    /// Render as a qr code
+    #[strum(serialize = "qr")]
+    Qr {
+        /// Emit an svg data uri instead of drawing the code in the terminal
+        #[clap(long)]
+        svg: bool,
+    },
+
+    /// Convert a query string to a json object
+    #[strum(serialize = "querystring-to-json")]
+    QuerystringToJson,
+
+    /// Rewrite a reddit link to sort by top
     #[strum(serialize = "reddit-top")]
-    RedditTop,
+    RedditTop {
+        #[clap(long, default_value = "all", value_parser = REDDIT_WINDOWS)]
+        window: String,
+    },
+
+    /// Print every match of a regex
+    #[strum(serialize = "regex-extract")]
+    RegexExtract {
+        /// Prints each match, or capture group 1 if the pattern has one
+        pattern: String,
+    },
+
+    /// Replace every match of a regex
+    #[strum(serialize = "regex-replace")]
+    RegexReplace {
+        pattern: String,
+
+        /// Can refer to capture groups with $1 or ${name}
+        replacement: String,
+    },
+
+    /// Reverse text
+    #[strum(serialize = "reverse")]
+    Reverse,
+
+    /// Convert between roman numerals and numbers
+    #[strum(serialize = "roman")]
+    Roman,
+
+    /// Rotate letters by 13
+    #[strum(serialize = "rot13")]
+    Rot13,
+
+    /// Rotate printable ascii by 47
+    #[strum(serialize = "rot47")]
+    Rot47,
 
+    /// Print the sha1 hash
+    #[strum(serialize = "sha1")]
+    Sha1,
+
+    /// Print the sha256 hash
+    #[strum(serialize = "sha256")]
+    Sha256,
+
+    /// Convert to a url slug
+    #[strum(serialize = "slugify")]
+    Slugify,
+
+    /// Convert to snake_case
+    #[strum(serialize = "snake-case")]
+    SnakeCase,
+
+    /// Convert leading spaces to tabs
+    #[strum(serialize = "spaces-to-tabs")]
+    SpacesToTabs {
+        #[clap(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        width: u32,
+    },
+
+    /// cOnVeRt To MoCkInG cAsE
     #[strum(serialize = "spongebob")]
     Spongebob,
 
+    /// Print the sha256 fingerprint of an ssh public key
+    #[strum(serialize = "ssh-fingerprint")]
+    SshFingerprint,
+
+    /// Expand tabs to spaces
+    #[strum(serialize = "tabs-to-spaces")]
+    TabsToSpaces {
+        #[clap(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        width: u32,
+    },
+
+    /// Print the current unix timestamp in seconds
     #[strum(serialize = "timestamp")]
     Timestamp,
 
+    /// Print the current unix timestamp in milliseconds
+    #[strum(serialize = "timestamp-ms")]
+    TimestampMs,
+
+    /// Print the current unix timestamp in nanoseconds
+    #[strum(serialize = "timestamp-ns")]
+    TimestampNs,
+
+    /// Convert a unix timestamp to a date
+    #[strum(serialize = "timestamp-parse")]
+    TimestampParse,
+
+    /// Describe a unix timestamp relative to now
+    #[strum(serialize = "timestamp-relative")]
+    TimestampRelative,
+
+    /// Convert to Title Case
+    #[strum(serialize = "title-case")]
+    TitleCase {
+        /// Leave all caps words like API as they are
+        #[clap(long)]
+        keep_acronyms: bool,
+    },
+
+    /// Convert line endings to crlf
+    #[strum(serialize = "to-crlf")]
+    ToCrlf,
+
+    /// Convert line endings to lf
+    #[strum(serialize = "to-lf")]
+    ToLf,
+
+    /// Convert toml to json
+    #[strum(serialize = "toml-to-json")]
+    TomlToJson,
+
+    /// Generate a totp code from a base32 secret
+    #[strum(serialize = "totp")]
+    Totp {
+        #[clap(long, default_value_t = 6, value_parser = clap::value_parser!(u32).range(6..=8))]
+        digits: u32,
+
+        /// Seconds each code is valid for
+        #[clap(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+        period: u64,
+
+        #[clap(long, default_value = "sha1", value_parser = TOTP_ALGORITHMS)]
+        algorithm: String,
+    },
+
+    /// Convert a date and time from one timezone to another
+    #[strum(serialize = "tz-convert")]
+    TzConvert {
+        /// Timezone of inputs without an offset
+        #[clap(long, default_value = "UTC")]
+        from: String,
+
+        #[clap(long)]
+        to: String,
+    },
+
+    /// Escape non-ascii characters as \u{...}
+    #[strum(serialize = "unicode-escape")]
+    UnicodeEscape,
+
+    /// Unescape \u{...} and \uXXXX sequences
+    #[strum(serialize = "unicode-unescape")]
+    UnicodeUnescape,
+
+    /// Decode percent encoding
+    #[strum(serialize = "url-decode")]
+    UrlDecode {
+        #[clap(long)]
+        space_as_plus: bool,
+    },
+
+    /// Percent encode
+    #[strum(serialize = "url-encode")]
+    UrlEncode {
+        #[clap(long)]
+        space_as_plus: bool,
+    },
+
+    /// Show the parts of a url as json
+    #[strum(serialize = "url-inspect")]
+    UrlInspect,
+
+    /// Normalize a url so equivalent links compare equal
+    #[strum(serialize = "url-normalize")]
+    UrlNormalize,
+
+    /// Generate a random uuid
     #[strum(serialize = "uuid4")]
     Uuid4,
 
+    /// Generate a name based uuid
+    #[strum(serialize = "uuid5")]
+    Uuid5 {
+        /// One of dns, url, oid, x500 or a custom namespace uuid
+        #[clap(long, default_value = "dns")]
+        namespace: String,
+    },
+
+    /// Show the version, variant and timestamp of a uuid
+    #[strum(serialize = "uuid-inspect")]
+    UuidInspect,
+
+    /// Generate a time ordered uuid
     #[strum(serialize = "uuid7")]
     Uuid7,
+
+    /// Convert yaml to json
+    #[strum(serialize = "yaml-to-json")]
+    YamlToJson,
 }
 
-fn config_espanso() -> String {
-    let exec_path = std::env::current_exe().unwrap();
+impl Command {
+    pub fn is_generator(&self) -> bool {
+        matches!(
+            self,
+            Command::Lorem { .. }
+                | Command::Mac { .. }
+                | Command::Passphrase { .. }
+                | Command::Password { .. }
+                | Command::Pin { .. }
+                | Command::Uuid4
+                | Command::Uuid7
+        )
+    }
 
-    Command::iter()
-        .filter_map(|item| match item {
-            Command::ConfigEspanso => None,
-            item => Some(format!(
+    // whitespace is the point of these, so their output isn't trimmed
+    pub fn is_whitespace_sensitive(&self) -> bool {
+        matches!(
+            self,
+            Command::NormalizeWhitespace { .. }
+                | Command::SpacesToTabs { .. }
+                | Command::TabsToSpaces { .. }
+                | Command::ToCrlf
+                | Command::ToLf
+        )
+    }
+
+    // these ignore the input, so neither the clipboard nor an inherited stdin pipe is read
+    pub fn reads_input(&self) -> bool {
+        !matches!(
+            self,
+            Command::ConfigAlfred
+                | Command::ConfigEspanso
+                | Command::ConfigRaycast
+                | Command::Datetime { .. }
+                | Command::Ip { .. }
+                | Command::IpLocal { .. }
+                | Command::List
+                | Command::Lorem { .. }
+                | Command::Mac { .. }
+                | Command::Passphrase { .. }
+                | Command::Pin { .. }
+                | Command::Timestamp
+                | Command::TimestampMs
+                | Command::TimestampNs
+                | Command::Uuid4
+                | Command::Uuid7
+        )
+    }
+
+    // the doc comment on each variant, shared with --help
+    pub fn description(&self) -> String {
+        Arguments::command()
+            .find_subcommand(self.to_string())
+            .and_then(|command| command.get_about())
+            .map(|about| about.to_string())
+            .unwrap_or_default()
+    }
+
+    // the config generators describe the other commands, so they leave themselves out
+    pub fn is_config(&self) -> bool {
+        matches!(
+            self,
+            Command::ConfigAlfred | Command::ConfigEspanso | Command::ConfigRaycast
+        )
+    }
+
+    // whether a trigger with no arguments can run it, asking clap keeps this right for commands
+    // with required arguments like hmac --key or diff <OTHER>, list is only useful in a terminal
+    pub fn runs_bare(&self) -> bool {
+        !matches!(self, Command::List)
+            && Arguments::try_parse_from([env!("CARGO_BIN_NAME"), &self.to_string()]).is_ok()
+    }
+
+    // reports describe the clipboard rather than transform it, so they leave it alone
+    pub fn is_report(&self) -> bool {
+        matches!(
+            self,
+            Command::CharInfo
+                | Command::Count
+                | Command::CronDescribe
+                | Command::Diff { .. }
+                | Command::Hexdump
+                | Command::List
+                | Command::Qr { svg: false }
+        )
+    }
+}
+
+// script filter output, run `narigama-utils config-alfred` from a script filter and pass the
+// selected item on to a run script action as argv
+fn config_alfred() -> Result<String> {
+    let exec_path = std::env::current_exe()?;
+
+    let items = Command::iter()
+        .filter(|item| !item.is_config() && item.runs_bare())
+        .map(|item| {
+            serde_json::json!({
+                "uid": item.to_string(),
+                "title": item.to_string(),
+                "subtitle": item.description(),
+                "autocomplete": item.to_string(),
+                "arg": [exec_path.display().to_string(), item.to_string()],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(
+        &serde_json::json!({ "items": items }),
+    )?)
+}
+
+fn config_espanso() -> Result<String> {
+    let exec_path = std::env::current_exe()?;
+
+    let config = Command::iter()
+        .filter(|item| !item.is_config() && item.runs_bare())
+        .map(|item| {
+            format!(
                 "
+  # {2}
   - trigger: \";{1}\"
     replace: \"{{{{output}}}}\"
     vars:
@@ -64,145 +886,406 @@ fn config_espanso() -> String {
             - {1}
 ",
                 exec_path.display(),
-                item
-            )),
+                item,
+                item.description()
+            )
         })
         .collect::<Vec<String>>()
-        .join("\n")
-}
+        .join("\n");
 
-pub fn now() -> jiff::Zoned {
-    jiff::Timestamp::now()
-        .in_tz("UTC")
-        .expect("Unable to generate timestamp")
+    Ok(config)
 }
 
-pub fn binary_decode(input: &str) -> String {
-    input
-        .trim()
-        .split(' ')
-        .map(|chunk| u8::from_str_radix(chunk, 2).unwrap() as char)
-        .collect::<_>()
+// one script command per block, raycast wants each saved as its own file in a script directory,
+// silent mode shows the last line of output as a toast
+fn config_raycast() -> Result<String> {
+    let exec_path = std::env::current_exe()?;
+
+    let config = Command::iter()
+        .filter(|item| !item.is_config() && item.runs_bare())
+        .map(|item| {
+            format!(
+                "#!/bin/bash
+
+# @raycast.schemaVersion 1
+# @raycast.title {1}
+# @raycast.mode silent
+# @raycast.description {2}
+# @raycast.packageName narigama-utils
+
+exec \"{0}\" {1}
+",
+                exec_path.display(),
+                item,
+                item.description()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Ok(config)
 }
 
-pub fn binary_encode(input: &str) -> String {
-    input
-        .bytes()
-        .map(|b| format!("{:0>8}", format!("{b:b}")))
+fn list() -> String {
+    let width = Command::iter()
+        .map(|item| item.to_string().len())
+        .max()
+        .unwrap_or_default();
+
+    Command::iter()
+        .map(|item| format!("{:width$}  {}", item.to_string(), item.description()))
         .collect::<Vec<_>>()
-        .join(" ")
+        .join("\n")
 }
 
-pub fn format_json(input: &str) -> String {
-    let json = serde_json::from_str::<serde_json::Value>(input)
-        .expect("Unable to parse json, check input");
-    serde_json::to_string_pretty(&json).expect("Unable to generate json")
-}
+fn password_length(input: &str) -> usize {
+    let input = input.trim();
 
-pub fn gen_password(input: &str) -> String {
-    let length = input.parse().unwrap_or(32);
+    match input.parse() {
+        Ok(length) => length,
+        Err(_) if input.is_empty() => DEFAULT_PASSWORD_LENGTH,
+        Err(_) => {
+            eprintln!("warning: {input:?} is not a valid length, using {DEFAULT_PASSWORD_LENGTH}");
+            DEFAULT_PASSWORD_LENGTH
+        }
+    }
+}
 
-    rand::rng()
-        .sample_iter(&rand::distr::Alphanumeric)
-        .take(length)
-        .map(char::from)
-        .collect()
+fn password_policy(symbols: bool, no_digits: bool, no_uppercase: bool) -> PasswordPolicy {
+    PasswordPolicy {
+        symbols,
+        digits: !no_digits,
+        uppercase: !no_uppercase,
+    }
 }
 
-pub fn reddit_top(input: &str) -> String {
-    // parse and trim end of path
-    let mut url = Url::parse(input).unwrap();
-    let path_trimmed = url.path().trim_end_matches("/").to_string();
-    url.set_path(&path_trimmed);
+// execute runs once per --count, so anything worth saying about the input is said here first,
+// passwords get their length resolved (and any warning printed) a single time
+fn prepare_input(command: &Command, input: &str) -> String {
+    match command {
+        Command::Password {
+            symbols,
+            no_digits,
+            no_uppercase,
+            entropy,
+        } => {
+            let length = password_length(input);
 
-    match ["/u/", "/user/"].iter().any(|p| url.path().starts_with(p)) {
-        true => {
-            url.path_segments_mut().unwrap().extend(["submitted"]);
-            url.query_pairs_mut()
-                .append_pair("sort", "top")
-                .finish()
-                .to_string()
-        }
-        false => {
-            if !url.path().contains("/comments/") {
-                url.path_segments_mut().unwrap().extend(["top"]);
+            if *entropy {
+                let policy = password_policy(*symbols, *no_digits, *no_uppercase);
+                eprintln!("entropy: {:.1} bits", password_entropy(length, &policy));
             }
 
-            url.query_pairs_mut()
-                .append_pair("sort", "top")
-                .append_pair("t", "all")
-                .finish()
-                .to_string()
+            length.to_string()
         }
+        _ => input.to_string(),
     }
 }
 
-pub fn get_ip_address() -> String {
-    ureq::get("https://ipv4.icanhazip.com/")
-        .call()
-        .expect("unable to request ip address")
-        .body_mut()
-        .read_to_string()
-        .expect("unable to parse body into utf8 string")
+fn open_clipboard(args: &Arguments) -> Option<Box<dyn Clipboard>> {
+    match args.stdin {
+        true => None,
+        false => clipboard::select_clipboard(),
+    }
 }
 
-pub fn spongebob(input: &str) -> String {
-    input
-        .chars()
-        .enumerate()
-        .map(|(i, c)| match i.rem_euclid(2) == 0 {
-            true => c.to_uppercase().to_string(),
-            false => c.to_lowercase().to_string(),
-        })
-        .collect()
-}
+fn read_stdin() -> Result<String> {
+    let mut input = String::new();
 
-pub fn get_iso_timestamp() -> String {
-    now().timestamp().to_string()
-}
+    // don't block generators like uuid4 waiting on a terminal
+    if !std::io::stdin().is_terminal() {
+        std::io::stdin().read_to_string(&mut input)?;
+    }
 
-pub fn gen_uuid4() -> String {
-    uuid::Uuid::new_v4().as_hyphenated().to_string()
+    Ok(input)
 }
 
-pub fn gen_uuid7() -> String {
-    let timestamp = now().timestamp();
+// the input along with where it came from, for --dry-run, a clipboard that can't be read is
+// dropped so the result isn't written back to it either
+fn read_input(
+    args: &Arguments,
+    clipboard: &mut Option<Box<dyn Clipboard>>,
+) -> Result<(String, &'static str)> {
+    if !args.command.reads_input() {
+        tracing::debug!("not reading input, the command doesn't use any");
+        return Ok((String::new(), "nowhere"));
+    }
 
-    uuid::Uuid::new_v7(uuid::Timestamp::from_unix(
-        uuid::NoContext,
-        timestamp.as_second() as _,
-        timestamp.subsec_nanosecond() as _,
-    ))
-    .as_hyphenated()
-    .to_string()
+    if let Some(input) = &args.input {
+        tracing::debug!("reading input from the command line");
+        return Ok((input.clone(), "the command line"));
+    }
+
+    match clipboard.as_mut().map(|clipboard| clipboard.get()) {
+        Some(Ok(input)) => Ok((input, "the clipboard")),
+        Some(Err(err)) => {
+            tracing::debug!(%err, "unable to read the clipboard, reading stdin instead");
+            *clipboard = None;
+            Ok((read_stdin()?, "stdin"))
+        }
+        None => {
+            tracing::debug!("reading input from stdin");
+            Ok((read_stdin()?, "stdin"))
+        }
+    }
 }
 
-pub fn main() {
-    let args = Arguments::parse();
+fn execute(command: &Command, input: &str) -> Result<String> {
+    let result = match command {
+        Command::ConfigAlfred => config_alfred()?,
+        Command::ConfigEspanso => config_espanso()?,
+        Command::ConfigRaycast => config_raycast()?,
+        Command::Aesthetic => aesthetic(input),
+        Command::BaseConvert { from, to } => base_convert(input, *from, *to)?,
+        Command::Base32Decode => base32_decode(input)?,
+        Command::Base32Encode => base32_encode(input),
+        Command::Base58Decode { check } => base58_decode(input, *check)?,
+        Command::Base58Encode { check } => base58_encode(input, *check),
+        Command::Base64Decode { url_safe } => base64_decode(input, *url_safe)?,
+        Command::Base64Encode { url_safe } => base64_encode(input, *url_safe),
+        Command::Bech32Decode => bech32_decode(input)?,
+        Command::Bech32Encode { hrp, bech32m } => bech32_encode(input, hrp, *bech32m)?,
+        Command::BinaryDecode => binary_decode(input)?,
+        Command::BinaryEncode => binary_encode(input),
+        Command::Caesar { shift } => caesar(input, *shift),
+        Command::CamelCase => camel_case(input),
+        Command::CapitalizeFirst => capitalize_first(input),
+        Command::CapitalizeLines => capitalize_lines(input),
+        Command::CharInfo => char_info(input),
+        Command::Color => convert_color(input)?,
+        Command::Completions { .. } => unreachable!("completions never read input"),
+        Command::Convert { to } => convert_units(input, to)?,
+        Command::Count => count(input),
+        Command::Crc32 { decimal } => crc32(input, *decimal),
+        Command::CronDescribe => describe_cron(input)?,
+        Command::CsvToJson => csv_to_json(input)?,
+        Command::DataUri { mime } => data_uri(input, mime)?,
+        Command::DataUriDecode => data_uri_decode(input)?,
+        Command::Datetime { format, tz } => format_datetime(format.as_deref(), tz)?,
+        Command::Diff { other, file } => {
+            let (other, name) = match (other, file) {
+                (_, Some(file)) => (std::fs::read_to_string(file)?, file.display().to_string()),
+                (other, None) => (other.clone().unwrap_or_default(), "other".to_string()),
+            };
+
+            diff(input, &other, &name, std::io::stdout().is_terminal())
+        }
+        Command::Duration => parse_duration(input)?,
+        Command::DurationFormat => format_duration(input)?,
+        Command::EmojiDecode => emoji_decode(input),
+        Command::EmojiEncode => emoji_encode(input),
+        Command::EnvToJson => env_to_json(input)?,
+        Command::FormatJson => format_json(input)?,
+        Command::FormatSql { uppercase } => format_sql(input, *uppercase),
+        Command::FormatXml => format_xml(input)?,
+        Command::GzipPack => gzip_pack(input)?,
+        Command::GzipUnpack => gzip_unpack(input)?,
+        Command::HexDecode => hex_decode(input)?,
+        Command::HexEncode { separator } => hex_encode(input, separator),
+        Command::Hexdump => hexdump(input),
+        Command::Hmac { key, algorithm } => hmac(input, key, algorithm)?,
+        Command::HtmlDecode => html_decode(input),
+        Command::HtmlEncode => html_encode(input),
+        Command::HtmlToMarkdown => html_to_markdown(input)?,
+        Command::Ip { v6, both } => match (v6, both) {
+            (_, true) => get_ip_addresses()?,
+            (true, _) => get_ip_address(IpVersion::V6)?,
+            (false, _) => get_ip_address(IpVersion::V4)?,
+        },
+        Command::IpLocal { all } => get_local_ip_addresses(*all)?,
+        Command::Isbn => isbn(input)?,
+        Command::JsonFlatten => flatten_json(input)?,
+        Command::JsonMinify => minify_json(input)?,
+        Command::JsonQuery { path } => query_json(input, path)?,
+        Command::JsonSortArray { by } => sort_json_array(input, by)?,
+        Command::JsonSortKeys => sort_json_keys(input)?,
+        Command::JsonStringEscape => json_string_escape(input)?,
+        Command::JsonStringUnescape => json_string_unescape(input)?,
+        Command::JsonToCsv => json_to_csv(input)?,
+        Command::JsonToEnv => json_to_env(input)?,
+        Command::JsonToQuerystring => json_to_querystring(input)?,
+        Command::JsonToToml => json_to_toml(input)?,
+        Command::JsonToYaml => json_to_yaml(input)?,
+        Command::JsonUnflatten => unflatten_json(input)?,
+        Command::JwtDecode => {
+            let decoded = decode_jwt(input)?;
+            eprintln!("warning: the signature has not been verified");
+            decoded
+        }
+        Command::KebabCase => kebab_case(input),
+        Command::Leet { level } => leet(input, *level),
+        Command::LinesDedup => lines_dedup(input),
+        Command::LinesReverse => lines_reverse(input),
+        Command::LinesShuffle => lines_shuffle(input),
+        Command::LinesSort => lines_sort(input),
+        Command::List => list(),
+        Command::Lorem { amount, unit } => gen_lorem(*amount, unit),
+        Command::Luhn => luhn(input)?,
+        Command::LuhnComplete => luhn_complete(input)?,
+        Command::Mac { oui } => gen_mac(oui.as_deref())?,
+        Command::MarkdownToHtml => markdown_to_html(input),
+        Command::Md5 => md5(input),
+        Command::MorseDecode => morse_decode(input)?,
+        Command::MorseEncode => morse_encode(input),
+        Command::Nato => nato(input),
+        Command::NormalizeWhitespace {
+            no_strip_trailing,
+            no_collapse_blank_lines,
+            no_final_newline,
+        } => {
+            let policy = WhitespacePolicy {
+                strip_trailing: !no_strip_trailing,
+                collapse_blank_lines: !no_collapse_blank_lines,
+                final_newline: !no_final_newline,
+            };
 
-    let mut clipboard =
-        copypasta::ClipboardContext::new().expect("Unable to build clipboard context");
-
-    let input = clipboard
-        .get_contents()
-        .expect("Unable to get contents of clipboard");
-
-    let result = match &args.command {
-        Command::ConfigEspanso => config_espanso(),
-        Command::BinaryDecode => binary_decode(&input),
-        Command::BinaryEncode => binary_encode(&input),
-        Command::FormatJson => format_json(&input),
-        Command::Ip => get_ip_address(),
-        Command::Password => gen_password(&input),
-        Command::RedditTop => reddit_top(&input),
-        Command::Spongebob => spongebob(&input),
+            normalize_whitespace(input, &policy)
+        }
+        Command::NumberFormat { separator, decimal } => number_format(input, separator, decimal)?,
+        Command::PascalCase => pascal_case(input),
+        Command::Passphrase { words, separator } => gen_passphrase(*words, separator),
+        Command::Password {
+            symbols,
+            no_digits,
+            no_uppercase,
+            ..
+        } => gen_password(
+            password_length(input),
+            &password_policy(*symbols, *no_digits, *no_uppercase),
+        ),
+        Command::Pin { length, no_weak } => gen_pin(*length as usize, *no_weak),
+        Command::PunycodeDecode => punycode_decode(input)?,
+        Command::PunycodeEncode => punycode_encode(input)?,
+        Command::Qr { svg } => qr_code(input, *svg)?,
+        Command::QuerystringToJson => querystring_to_json(input)?,
+        Command::RedditTop { window } => reddit_top(input, window)?,
+        Command::RegexExtract { pattern } => regex_extract(input, pattern)?,
+        Command::RegexReplace {
+            pattern,
+            replacement,
+        } => regex_replace(input, pattern, replacement)?,
+        Command::Reverse => reverse(input),
+        Command::Roman => roman(input)?,
+        Command::Rot13 => rot13(input),
+        Command::Rot47 => rot47(input),
+        Command::Sha1 => sha1(input),
+        Command::Sha256 => sha256(input),
+        Command::Slugify => slugify(input),
+        Command::SnakeCase => snake_case(input),
+        Command::SpacesToTabs { width } => spaces_to_tabs(input, *width as usize),
+        Command::Spongebob => spongebob(input),
+        Command::SshFingerprint => ssh_fingerprint(input)?,
+        Command::TabsToSpaces { width } => tabs_to_spaces(input, *width as usize),
         Command::Timestamp => get_iso_timestamp(),
+        Command::TimestampMs => get_epoch_millis(),
+        Command::TimestampNs => get_epoch_nanos(),
+        Command::TimestampParse => parse_timestamp(input)?,
+        Command::TimestampRelative => relative_timestamp(input)?,
+        Command::TitleCase { keep_acronyms } => title_case(input, *keep_acronyms),
+        Command::ToCrlf => to_crlf(input),
+        Command::ToLf => to_lf(input),
+        Command::TomlToJson => toml_to_json(input)?,
+        Command::Totp {
+            digits,
+            period,
+            algorithm,
+        } => {
+            let (code, remaining) = gen_totp(input, *digits, *period, algorithm)?;
+            eprintln!("rotates in {remaining}s");
+            code
+        }
+        Command::TzConvert { from, to } => convert_timezone(input, from, to)?,
+        Command::UnicodeEscape => unicode_escape(input),
+        Command::UnicodeUnescape => unicode_unescape(input)?,
+        Command::UrlDecode { space_as_plus } => url_decode(input, *space_as_plus)?,
+        Command::UrlEncode { space_as_plus } => url_encode(input, *space_as_plus),
+        Command::UrlInspect => inspect_url(input)?,
+        Command::UrlNormalize => normalize_url(input)?,
         Command::Uuid4 => gen_uuid4(),
+        Command::Uuid5 { namespace } => gen_uuid5(input, namespace)?,
+        Command::UuidInspect => inspect_uuid(input)?,
         Command::Uuid7 => gen_uuid7(),
+        Command::YamlToJson => yaml_to_json(input)?,
+    };
+
+    Ok(result)
+}
+
+fn run(args: &Arguments) -> Result<()> {
+    if let Command::Completions { shell } = args.command {
+        let mut command = Arguments::command();
+        let name = command.get_name().to_string();
+        clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+        return Ok(());
+    }
+
+    let mut clipboard = open_clipboard(args);
+    let (input, source) = read_input(args, &mut clipboard)?;
+    tracing::debug!(command = %args.command, length = input.len(), "running");
+
+    let started = std::time::Instant::now();
+    let prepared = prepare_input(&args.command, &input);
+    let result = (0..args.count)
+        .map(|_| execute(&args.command, &prepared))
+        .collect::<Result<Vec<_>>>()?
+        .join("\n");
+    tracing::debug!(elapsed = ?started.elapsed(), length = result.len(), "finished");
+
+    // only the end is trimmed, leading indentation is part of the text
+    let output = match args.command.is_whitespace_sensitive() {
+        true => result.as_str(),
+        false => result.trim_end(),
     };
 
-    print! {"{}", result.trim()};
-    clipboard
-        .set_contents(result)
-        .expect("Unable to set contents of clipboard")
+    if args.dry_run {
+        match args.command.reads_input() {
+            true => eprintln!("{}: read {} bytes from {source}", args.command, input.len()),
+            false => eprintln!("{}: takes no input", args.command),
+        }
+        for line in output.lines() {
+            eprintln!("{}: {line}", args.command);
+        }
+        return Ok(());
+    }
+
+    print! {"{}", output};
+
+    match clipboard {
+        Some(mut clipboard) if !args.no_clipboard && !args.command.is_report() => {
+            clipboard.set(output)
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn main() {
+    let args = Arguments::parse();
+
+    // silent unless asked, everything goes to stderr so the output stays clean
+    if args.verbose {
+        tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(std::io::stderr)
+            .init();
+    }
+
+    if args.count > 1 && !args.command.is_generator() {
+        // completions is disabled in strum, so it can't be displayed
+        let name = match args.command {
+            Command::Completions { .. } => "completions".to_string(),
+            ref command => command.to_string(),
+        };
+
+        Arguments::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                format!("--count is not supported by {name}"),
+            )
+            .exit();
+    }
+
+    if let Err(err) = run(&args) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,22 @@
+use crate::Result;
+
+// a fragment rather than a whole document, ready to paste into something else
+pub fn markdown_to_html(input: &str) -> String {
+    let options =
+        pulldown_cmark::Options::ENABLE_TABLES | pulldown_cmark::Options::ENABLE_STRIKETHROUGH;
+    let parser = pulldown_cmark::Parser::new_ext(input, options);
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+// tags without a markdown equivalent are replaced by their text content, except for the ones
+// whose content was never meant to be read
+pub fn html_to_markdown(input: &str) -> Result<String> {
+    let converter = htmd::HtmlToMarkdown::builder()
+        .skip_tags(vec!["head", "script", "style"])
+        .build();
+
+    Ok(converter.convert(input)?)
+}
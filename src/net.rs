@@ -0,0 +1,271 @@
+use crate::{Error, Result, query_pairs_to_json};
+use url::Url;
+
+pub const REDDIT_WINDOWS: [&str; 6] = ["hour", "day", "week", "month", "year", "all"];
+
+fn is_reddit_host(host: &str) -> bool {
+    host == "reddit.com" || host.ends_with(".reddit.com")
+}
+
+pub fn reddit_top(input: &str, window: &str) -> Result<String> {
+    if !REDDIT_WINDOWS.contains(&window) {
+        return Err(Error::Input(format!(
+            "{window:?} is not a valid window, expected one of {}",
+            REDDIT_WINDOWS.join(", ")
+        )));
+    }
+
+    let mut url = Url::parse(input.trim())?;
+
+    // any subdomain (www, old, new, np...) is kept as the user had it
+    if !url.host_str().is_some_and(is_reddit_host) {
+        return Err(Error::Input(format!("{url} is not a reddit link")));
+    }
+
+    // drop empty segments so trailing slashes don't matter, match case-insensitively but keep
+    // the original casing of subreddit and user names
+    let segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let lowered = segments
+        .iter()
+        .map(|segment| segment.to_lowercase())
+        .collect::<Vec<_>>();
+    let lowered = lowered.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let (path, query) = match lowered.as_slice() {
+        // user pages list everything they've submitted, all time unless asked otherwise
+        ["u" | "user", _, ..] => (
+            [&segments[..2], &["submitted".to_string()]].concat(),
+            match window {
+                "all" => vec![("sort", "top")],
+                window => vec![("sort", "top"), ("t", window)],
+            },
+        ),
+        // comment permalinks, sort the comments themselves, matched by position so a subreddit
+        // called r/comments is still treated as a subreddit
+        ["r", _, "comments", ..] | ["comments", ..] => {
+            (segments, vec![("sort", "top"), ("t", window)])
+        }
+        // subreddits, replacing any existing listing like /hot or /new
+        ["r", _, ..] => (
+            [&segments[..2], &["top".to_string()]].concat(),
+            vec![("sort", "top"), ("t", window)],
+        ),
+        _ => (
+            [&segments[..], &["top".to_string()]].concat(),
+            vec![("sort", "top"), ("t", window)],
+        ),
+    };
+
+    url.set_query(None);
+    url.set_fragment(None);
+    url.path_segments_mut().unwrap().clear().extend(path);
+    url.query_pairs_mut().extend_pairs(query);
+
+    Ok(url.to_string())
+}
+
+// `example.com/path` and `localhost:8080` are easy to paste without a scheme, the first fails
+// to parse and the second parses with `localhost` as the scheme
+fn parse_url(input: &str) -> Result<Url> {
+    let input = input.trim();
+    let guidance = || {
+        Error::Input(format!(
+            "{input:?} has no scheme, try \"https://{input}\" instead"
+        ))
+    };
+
+    match Url::parse(input) {
+        Err(url::ParseError::RelativeUrlWithoutBase) => Err(guidance()),
+        Ok(url)
+            if url.cannot_be_a_base() && url.path().starts_with(|c: char| c.is_ascii_digit()) =>
+        {
+            Err(guidance())
+        }
+        url => Ok(url?),
+    }
+}
+
+pub fn inspect_url(input: &str) -> Result<String> {
+    let url = parse_url(input)?;
+
+    let inspected = serde_json::json!({
+        "scheme": url.scheme(),
+        "username": Some(url.username()).filter(|username| !username.is_empty()),
+        "password": url.password(),
+        "host": url.host_str(),
+        "port": url.port_or_known_default(),
+        "path": url.path(),
+        "query": query_pairs_to_json(url.query_pairs()),
+        "fragment": url.fragment(),
+    });
+
+    Ok(serde_json::to_string_pretty(&inspected)?)
+}
+
+// the url crate already lowercases hosts and drops default ports for http(s) and friends, this
+// also lowercases hosts for other schemes and sorts the query so equivalent links compare equal
+pub fn normalize_url(input: &str) -> Result<String> {
+    let mut url = parse_url(input)?;
+
+    if let Some(host) = url.host_str().map(str::to_lowercase) {
+        url.set_host(Some(&host))?;
+    }
+
+    // pairs are sorted as written, going through form encoding would turn %20 into + and change
+    // what some servers see, sort_by_key is stable so repeated keys keep their relative order
+    let mut query = url
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    query.sort_by_key(|pair| pair.split('=').next().unwrap_or_default().to_string());
+
+    let query = query.join("&");
+    url.set_query(Some(query.as_str()).filter(|query| !query.is_empty()));
+
+    Ok(url.to_string())
+}
+
+// idna's errors don't say what went wrong, so there's little point passing them on
+fn invalid_domain(input: &str) -> Error {
+    Error::Input(format!("{input:?} is not a valid domain name"))
+}
+
+// each label is converted on its own, so `münchen.de` becomes `xn--mnchen-3ya.de`
+pub fn punycode_encode(input: &str) -> Result<String> {
+    let input = input.trim();
+    idna::domain_to_ascii_strict(input).map_err(|_| invalid_domain(input))
+}
+
+pub fn punycode_decode(input: &str) -> Result<String> {
+    let input = input.trim();
+    match idna::domain_to_unicode(input) {
+        (domain, Ok(())) => Ok(domain),
+        (_, Err(_)) => Err(invalid_domain(input)),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    fn endpoint(self) -> &'static str {
+        match self {
+            IpVersion::V4 => "https://ipv4.icanhazip.com/",
+            IpVersion::V6 => "https://ipv6.icanhazip.com/",
+        }
+    }
+}
+
+pub fn get_ip_address(version: IpVersion) -> Result<String> {
+    tracing::debug!(endpoint = version.endpoint(), "requesting public address");
+
+    let address = ureq::get(version.endpoint())
+        .call()?
+        .body_mut()
+        .read_to_string()?;
+
+    Ok(address.trim().to_string())
+}
+
+// report whichever of v4 and v6 are reachable, only failing if neither is
+pub fn get_ip_addresses() -> Result<String> {
+    match (get_ip_address(IpVersion::V4), get_ip_address(IpVersion::V6)) {
+        (Ok(v4), Ok(v6)) => Ok(format!("{v4}\n{v6}")),
+        (Ok(address), Err(_)) | (Err(_), Ok(address)) => Ok(address),
+        (Err(err), Err(_)) => Err(err),
+    }
+}
+
+pub fn get_local_ip_addresses(all: bool) -> Result<String> {
+    let interfaces = if_addrs::get_if_addrs()?
+        .into_iter()
+        .filter(|interface| interface.oper_status != if_addrs::IfOperStatus::Down)
+        .filter(|interface| !interface.is_loopback())
+        .collect::<Vec<_>>();
+
+    if all {
+        return Ok(interfaces
+            .iter()
+            .map(|interface| format!("{}: {}", interface.name, interface.ip()))
+            .collect::<Vec<_>>()
+            .join("\n"));
+    }
+
+    interfaces
+        .iter()
+        .find(|interface| interface.ip().is_ipv4() && !interface.is_link_local())
+        .map(|interface| interface.ip().to_string())
+        .ok_or_else(|| {
+            Error::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "no non-loopback ipv4 interface is up",
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reddit_top_user_pages() {
+        assert_eq!(
+            reddit_top("https://www.reddit.com/user/spez/", "all").unwrap(),
+            "https://www.reddit.com/user/spez/submitted?sort=top"
+        );
+        assert_eq!(
+            reddit_top("https://old.reddit.com/u/spez/comments", "week").unwrap(),
+            "https://old.reddit.com/u/spez/submitted?sort=top&t=week"
+        );
+    }
+
+    #[test]
+    fn reddit_top_subreddit_pages() {
+        assert_eq!(
+            reddit_top("https://www.reddit.com/r/rust/new/", "month").unwrap(),
+            "https://www.reddit.com/r/rust/top?sort=top&t=month"
+        );
+        assert_eq!(
+            reddit_top("https://www.reddit.com/r/comments/", "all").unwrap(),
+            "https://www.reddit.com/r/comments/top?sort=top&t=all"
+        );
+    }
+
+    #[test]
+    fn reddit_top_comment_permalinks() {
+        assert_eq!(
+            reddit_top(
+                "https://www.reddit.com/r/rust/comments/abc123/some_title/?utm_source=share",
+                "all"
+            )
+            .unwrap(),
+            "https://www.reddit.com/r/rust/comments/abc123/some_title?sort=top&t=all"
+        );
+    }
+
+    #[test]
+    fn normalize_url_keeps_query_encoding() {
+        assert_eq!(
+            normalize_url("https://Example.com/path?q=a%20b&a=1&q=c+d").unwrap(),
+            "https://example.com/path?a=1&q=a%20b&q=c+d"
+        );
+    }
+
+    #[test]
+    fn reddit_top_rejects_other_hosts() {
+        assert!(reddit_top("https://example.com/r/rust", "all").is_err());
+        assert!(reddit_top("https://www.reddit.com/r/rust", "decade").is_err());
+    }
+}
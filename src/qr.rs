@@ -0,0 +1,23 @@
+use crate::{Result, base64_encode};
+use qrcode::QrCode;
+use qrcode::render::{svg, unicode};
+
+// light modules are drawn as blocks so the quiet zone isn't whitespace that gets trimmed, which
+// also makes the code scan properly on a dark terminal
+pub fn qr_code(input: &str, svg: bool) -> Result<String> {
+    let code = QrCode::new(input.trim().as_bytes())?;
+
+    if svg {
+        let image = code.render::<svg::Color>().min_dimensions(256, 256).build();
+        return Ok(format!(
+            "data:image/svg+xml;base64,{}",
+            base64_encode(&image, false)
+        ));
+    }
+
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
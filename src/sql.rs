@@ -0,0 +1,11 @@
+// best effort, sqlformat only moves whitespace around and optionally changes keyword case,
+// every token of the original query is kept
+pub fn format_sql(input: &str, uppercase: bool) -> String {
+    let options = sqlformat::FormatOptions {
+        indent: sqlformat::Indent::Spaces(2),
+        uppercase: uppercase.then_some(true),
+        ..Default::default()
+    };
+
+    sqlformat::format(input.trim(), &sqlformat::QueryParams::None, &options)
+}
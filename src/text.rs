@@ -0,0 +1,653 @@
+use crate::Result;
+use rand::seq::SliceRandom;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub fn spongebob(input: &str) -> String {
+    input
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match i.rem_euclid(2) == 0 {
+            true => c.to_uppercase().to_string(),
+            false => c.to_lowercase().to_string(),
+        })
+        .collect()
+}
+
+// printable ascii has a fullwidth twin at a fixed offset, space is the odd one out
+pub fn aesthetic(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            ' ' => '\u{3000}',
+            '!'..='~' => char::from_u32(c as u32 - '!' as u32 + 0xFF01).unwrap_or(c),
+            c => c,
+        })
+        .collect()
+}
+
+// each substitution lists the lowest level it kicks in at
+const LEET: [(char, &str, u8); 20] = [
+    ('a', "4", 1),
+    ('e', "3", 1),
+    ('o', "0", 1),
+    ('t', "7", 1),
+    ('i', "1", 2),
+    ('s', "5", 2),
+    ('l', "1", 2),
+    ('g', "9", 2),
+    ('b', "8", 2),
+    ('z', "2", 2),
+    ('c', "(", 3),
+    ('d', "|)", 3),
+    ('h', "#", 3),
+    ('k', "|<", 3),
+    ('m', "|\\/|", 3),
+    ('n', "|\\|", 3),
+    ('u', "|_|", 3),
+    ('v', "\\/", 3),
+    ('w', "\\/\\/", 3),
+    ('x', "><", 3),
+];
+
+pub fn leet(input: &str, level: u8) -> String {
+    input
+        .chars()
+        .map(|c| {
+            LEET.iter()
+                .find(|(letter, _, min)| *letter == c.to_ascii_lowercase() && *min <= level)
+                .map(|(_, replacement, _)| replacement.to_string())
+                .unwrap_or_else(|| c.to_string())
+        })
+        .collect()
+}
+
+const NATO: [(char, &str); 48] = [
+    ('A', "Alpha"),
+    ('B', "Bravo"),
+    ('C', "Charlie"),
+    ('D', "Delta"),
+    ('E', "Echo"),
+    ('F', "Foxtrot"),
+    ('G', "Golf"),
+    ('H', "Hotel"),
+    ('I', "India"),
+    ('J', "Juliet"),
+    ('K', "Kilo"),
+    ('L', "Lima"),
+    ('M', "Mike"),
+    ('N', "November"),
+    ('O', "Oscar"),
+    ('P', "Papa"),
+    ('Q', "Quebec"),
+    ('R', "Romeo"),
+    ('S', "Sierra"),
+    ('T', "Tango"),
+    ('U', "Uniform"),
+    ('V', "Victor"),
+    ('W', "Whiskey"),
+    ('X', "X-ray"),
+    ('Y', "Yankee"),
+    ('Z', "Zulu"),
+    ('0', "Zero"),
+    ('1', "One"),
+    ('2', "Two"),
+    ('3', "Three"),
+    ('4', "Four"),
+    ('5', "Five"),
+    ('6', "Six"),
+    ('7', "Seven"),
+    ('8', "Eight"),
+    ('9', "Nine"),
+    (' ', "Space"),
+    ('.', "Dot"),
+    (',', "Comma"),
+    ('-', "Dash"),
+    ('_', "Underscore"),
+    ('/', "Slash"),
+    (':', "Colon"),
+    ('@', "At"),
+    ('#', "Hash"),
+    ('+', "Plus"),
+    ('!', "Exclamation"),
+    ('?', "Question"),
+];
+
+// characters without a code word are passed through as-is
+pub fn nato(input: &str) -> String {
+    input
+        .trim()
+        .chars()
+        .map(|c| {
+            NATO.iter()
+                .find(|(letter, _)| *letter == c.to_ascii_uppercase())
+                .map(|(_, word)| word.to_string())
+                .unwrap_or_else(|| c.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn caesar(input: &str, shift: i32) -> String {
+    let shift = shift.rem_euclid(26) as u8;
+
+    input
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => ((c as u8 - b'a' + shift) % 26 + b'a') as char,
+            'A'..='Z' => ((c as u8 - b'A' + shift) % 26 + b'A') as char,
+            c => c,
+        })
+        .collect()
+}
+
+pub fn rot13(input: &str) -> String {
+    caesar(input, 13)
+}
+
+fn is_shortcode(input: &str) -> bool {
+    !input.is_empty()
+        && input
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-'))
+}
+
+// anything between colons that isn't a known shortcode is left as it was, so times like 12:30
+// and unknown codes survive
+pub fn emoji_encode(input: &str) -> String {
+    let mut output = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find(':') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let emoji = rest[1..]
+            .find(':')
+            .filter(|end| is_shortcode(&rest[1..end + 1]))
+            .and_then(|end| Some((emojis::get_by_shortcode(&rest[1..end + 1])?, end)));
+
+        match emoji {
+            Some((emoji, end)) => {
+                output.push_str(emoji.as_str());
+                rest = &rest[end + 2..];
+            }
+            None => {
+                output.push(':');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+// emoji without a shortcode, like most skin tone variants, are left as they are
+pub fn emoji_decode(input: &str) -> String {
+    input
+        .graphemes(true)
+        .map(
+            |grapheme| match emojis::get(grapheme).and_then(|emoji| emoji.shortcode()) {
+                Some(shortcode) => format!(":{shortcode}:"),
+                None => grapheme.to_string(),
+            },
+        )
+        .collect()
+}
+
+// by grapheme, so flags, skin tones and combining accents stay in one piece
+pub fn reverse(input: &str) -> String {
+    input.graphemes(true).rev().collect()
+}
+
+// rotates everything from '!' to '~', so digits and punctuation are obscured too
+pub fn rot47(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            '!'..='~' => ((c as u8 - b'!' + 47) % 94 + b'!') as char,
+            c => c,
+        })
+        .collect()
+}
+
+pub fn slugify(input: &str) -> String {
+    deunicode::deunicode(input)
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// split on anything non-alphanumeric and on case boundaries, so "myHTTPServer" becomes
+// ["my", "HTTP", "Server"]
+fn split_words(input: &str) -> Vec<String> {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        let boundary = c.is_uppercase()
+            && match i.checked_sub(1).map(|i| chars[i]) {
+                Some(prev) if prev.is_lowercase() || prev.is_numeric() => true,
+                Some(prev) if prev.is_uppercase() => {
+                    chars.get(i + 1).is_some_and(|n| n.is_lowercase())
+                }
+                _ => false,
+            };
+
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+
+        word.push(c);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+const TITLE_STOP_WORDS: [&str; 17] = [
+    "a", "an", "and", "as", "at", "but", "by", "for", "in", "nor", "of", "on", "or", "the", "to",
+    "via", "vs",
+];
+
+// stop words stay lowercase unless they start the text, whitespace is kept as it was
+pub fn title_case(input: &str, keep_acronyms: bool) -> String {
+    let mut first = true;
+
+    input
+        .split_inclusive(char::is_whitespace)
+        .map(|chunk| {
+            let word = chunk.trim_end();
+            let whitespace = &chunk[word.len()..];
+            let lowered = word.to_lowercase();
+
+            let is_acronym = word.chars().count() > 1
+                && word.chars().any(char::is_alphabetic)
+                && !word.chars().any(char::is_lowercase);
+
+            let word = match () {
+                _ if !first && TITLE_STOP_WORDS.contains(&lowered.as_str()) => lowered,
+                _ if keep_acronyms && is_acronym => word.to_string(),
+                _ => capitalize(word),
+            };
+            first &= word.is_empty();
+
+            format!("{word}{whitespace}")
+        })
+        .collect()
+}
+
+pub fn camel_case(input: &str) -> String {
+    split_words(input)
+        .iter()
+        .enumerate()
+        .map(|(i, word)| match i {
+            0 => word.to_lowercase(),
+            _ => capitalize(word),
+        })
+        .collect()
+}
+
+pub fn kebab_case(input: &str) -> String {
+    split_words(input)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub fn pascal_case(input: &str) -> String {
+    split_words(input)
+        .iter()
+        .map(|word| capitalize(word))
+        .collect()
+}
+
+pub fn snake_case(input: &str) -> String {
+    split_words(input)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+// prints capture group 1 when the pattern has one, otherwise the whole match
+pub fn regex_extract(input: &str, pattern: &str) -> Result<String> {
+    let regex = regex::Regex::new(pattern)?;
+
+    let matches = match regex.captures_len() > 1 {
+        true => regex
+            .captures_iter(input)
+            .filter_map(|captures| captures.get(1))
+            .map(|group| group.as_str())
+            .collect::<Vec<_>>(),
+        false => regex.find_iter(input).map(|found| found.as_str()).collect(),
+    };
+
+    Ok(matches.join("\n"))
+}
+
+// `$1` and `${name}` in the replacement refer to capture groups, `$$` is a literal dollar
+pub fn regex_replace(input: &str, pattern: &str, replacement: &str) -> Result<String> {
+    let regex = regex::Regex::new(pattern)?;
+    Ok(regex.replace_all(input, replacement).into_owned())
+}
+
+#[derive(Debug, Clone)]
+pub struct WhitespacePolicy {
+    pub strip_trailing: bool,
+    pub collapse_blank_lines: bool,
+    pub final_newline: bool,
+}
+
+impl Default for WhitespacePolicy {
+    fn default() -> Self {
+        Self {
+            strip_trailing: true,
+            collapse_blank_lines: true,
+            final_newline: true,
+        }
+    }
+}
+
+const COLLAPSE_BLANK_LINES: usize = 3;
+
+pub fn normalize_whitespace(input: &str, policy: &WhitespacePolicy) -> String {
+    let mut lines = input
+        .lines()
+        .map(|line| match policy.strip_trailing {
+            true => line.trim_end(),
+            false => line,
+        })
+        .collect::<Vec<_>>();
+
+    // runs of COLLAPSE_BLANK_LINES or more blank lines become a single one, shorter runs are
+    // usually deliberate spacing and are kept
+    if policy.collapse_blank_lines {
+        let mut collapsed = Vec::with_capacity(lines.len());
+
+        for run in lines.chunk_by(|a, b| a.trim().is_empty() == b.trim().is_empty()) {
+            match run[0].trim().is_empty() && run.len() >= COLLAPSE_BLANK_LINES {
+                true => collapsed.push(run[0]),
+                false => collapsed.extend_from_slice(run),
+            }
+        }
+
+        lines = collapsed;
+    }
+
+    // lines() drops the last line break, without final_newline it's put back as it was
+    let mut output = lines.join("\n");
+    if policy.final_newline {
+        output.truncate(output.trim_end_matches('\n').len());
+        output.push('\n');
+    } else if input.ends_with('\n') {
+        output.push('\n');
+    }
+
+    output
+}
+
+// crlf is collapsed first so it doesn't become two line breaks, then any lone cr is treated as
+// an old mac style line ending
+fn line_endings(input: &str, ending: &str) -> String {
+    input
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', ending)
+}
+
+pub fn to_lf(input: &str) -> String {
+    line_endings(input, "\n")
+}
+
+pub fn to_crlf(input: &str) -> String {
+    line_endings(input, "\r\n")
+}
+
+// a tab moves to the next tab stop rather than always adding `width` spaces
+pub fn tabs_to_spaces(input: &str, width: usize) -> String {
+    input
+        .split('\n')
+        .map(|line| {
+            let mut output = String::new();
+            let mut column = 0;
+
+            for c in line.chars() {
+                match c {
+                    '\t' => {
+                        let spaces = width - column % width;
+                        output.extend(std::iter::repeat_n(' ', spaces));
+                        column += spaces;
+                    }
+                    c => {
+                        output.push(c);
+                        column += 1;
+                    }
+                }
+            }
+
+            output
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// only the indentation is converted, spaces inside a line are usually alignment
+pub fn spaces_to_tabs(input: &str, width: usize) -> String {
+    input
+        .split('\n')
+        .map(|line| {
+            let content = line.trim_start_matches([' ', '\t']);
+            let indent = &line[..line.len() - content.len()];
+            let columns = indent.chars().fold(0, |column, c| match c {
+                '\t' => column + width - column % width,
+                _ => column + 1,
+            });
+
+            format!(
+                "{}{}{content}",
+                "\t".repeat(columns / width),
+                " ".repeat(columns % width)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn with_final_newline(input: &str) -> String {
+    match input.is_empty() || input.ends_with('\n') {
+        true => input.to_string(),
+        false => format!("{input}\n"),
+    }
+}
+
+// clipboards rarely keep a final newline where files usually have one, so a missing one isn't
+// reported as a change
+pub fn diff(input: &str, other: &str, other_name: &str, color: bool) -> String {
+    let (old, new) = (with_final_newline(input), with_final_newline(other));
+    let diff = similar::TextDiff::from_lines(&old, &new)
+        .unified_diff()
+        .header("input", other_name)
+        .to_string();
+
+    if !color {
+        return diff;
+    }
+
+    diff.lines()
+        .map(|line| match line {
+            line if line.starts_with("---") || line.starts_with("+++") => {
+                format!("\x1b[1m{line}\x1b[0m")
+            }
+            line if line.starts_with("@@") => format!("\x1b[36m{line}\x1b[0m"),
+            line if line.starts_with('-') => format!("\x1b[31m{line}\x1b[0m"),
+            line if line.starts_with('+') => format!("\x1b[32m{line}\x1b[0m"),
+            line => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const CHAR_INFO_LIMIT: usize = 256;
+
+// invisible and control characters are shown escaped so they can actually be seen
+pub fn char_info(input: &str) -> String {
+    let mut lines = input
+        .chars()
+        .take(CHAR_INFO_LIMIT)
+        .map(|c| {
+            let category = unicode_general_category::get_general_category(c);
+            let shown = match category.abbreviation().starts_with(['C', 'Z']) && c != ' ' {
+                true => c.escape_unicode().to_string(),
+                false => c.to_string(),
+            };
+            let name = unicode_names2::name(c)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| "<unnamed>".to_string());
+
+            format!(
+                "U+{:04X}\t{shown}\t{name}\t{} ({category:?})",
+                c as u32,
+                category.abbreviation()
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let total = input.chars().count();
+    if total > CHAR_INFO_LIMIT {
+        lines.push(format!("... and {} more", total - CHAR_INFO_LIMIT));
+    }
+
+    lines.join("\n")
+}
+
+// str::lines handles both \n and \r\n, and doesn't yield a trailing empty line
+fn map_lines<'a, S: std::borrow::Borrow<str>>(
+    input: &'a str,
+    f: impl FnOnce(Vec<&'a str>) -> Vec<S>,
+) -> String {
+    f(input.lines().collect()).join("\n")
+}
+
+// only the first letter changes, so bullets, numbering and indentation are kept
+pub fn capitalize_first(input: &str) -> String {
+    match input.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, c)) => format!(
+            "{}{}{}",
+            &input[..i],
+            c.to_uppercase(),
+            &input[i + c.len_utf8()..]
+        ),
+        None => input.to_string(),
+    }
+}
+
+pub fn capitalize_lines(input: &str) -> String {
+    map_lines(input, |lines| {
+        lines.into_iter().map(capitalize_first).collect()
+    })
+}
+
+pub fn lines_sort(input: &str) -> String {
+    map_lines(input, |mut lines| {
+        lines.sort();
+        lines
+    })
+}
+
+// keeps the first occurrence of each line, in its original position
+pub fn lines_dedup(input: &str) -> String {
+    let mut seen = std::collections::HashSet::new();
+    map_lines(input, |lines| {
+        lines
+            .into_iter()
+            .filter(|line| seen.insert(*line))
+            .collect()
+    })
+}
+
+pub fn lines_shuffle(input: &str) -> String {
+    map_lines(input, |mut lines| {
+        lines.shuffle(&mut rand::rng());
+        lines
+    })
+}
+
+pub fn lines_reverse(input: &str) -> String {
+    map_lines(input, |lines| lines.into_iter().rev().collect())
+}
+
+// characters are graphemes, so an emoji with modifiers or an accented letter counts once
+pub fn count(input: &str) -> String {
+    let stats = [
+        ("lines", input.lines().count()),
+        ("words", input.split_whitespace().count()),
+        ("chars", input.graphemes(true).count()),
+        ("bytes", input.len()),
+    ];
+    let width = stats
+        .iter()
+        .map(|(_, n)| n.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    stats
+        .iter()
+        .map(|(label, n)| format!("{label}: {n:>width$}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_keeps_graphemes_whole() {
+        assert_eq!(reverse("a🇬🇧"), "🇬🇧a");
+        assert_eq!(reverse(&reverse("🇬🇧a👨‍👩‍👧")), "🇬🇧a👨‍👩‍👧");
+    }
+
+    #[test]
+    fn normalize_whitespace_only_collapses_long_blank_runs() {
+        let policy = WhitespacePolicy::default();
+        assert_eq!(normalize_whitespace("a\n\n\nb", &policy), "a\n\n\nb\n");
+        assert_eq!(normalize_whitespace("a\n\n\n\nb", &policy), "a\n\nb\n");
+    }
+
+    #[test]
+    fn normalize_whitespace_keeps_an_existing_final_newline() {
+        let policy = WhitespacePolicy {
+            final_newline: false,
+            ..WhitespacePolicy::default()
+        };
+        assert_eq!(normalize_whitespace("a  \nb\n", &policy), "a\nb\n");
+        assert_eq!(normalize_whitespace("a  \nb", &policy), "a\nb");
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Error, Result};
+
+pub fn now() -> jiff::Zoned {
+    jiff::Timestamp::now().to_zoned(jiff::tz::TimeZone::UTC)
+}
+
+// a strftime style format string, or rfc 3339 when none is given
+pub fn format_datetime(format: Option<&str>, tz: &str) -> Result<String> {
+    let now = now().with_time_zone(jiff::tz::TimeZone::get(tz)?);
+
+    match format {
+        Some(format) => Ok(jiff::fmt::strtime::format(format, &now)?),
+        None => Ok(now
+            .timestamp()
+            .display_with_offset(now.offset())
+            .to_string()),
+    }
+}
+
+pub fn get_iso_timestamp() -> String {
+    now().timestamp().to_string()
+}
+
+pub fn get_epoch_millis() -> String {
+    now().timestamp().as_millisecond().to_string()
+}
+
+pub fn get_epoch_nanos() -> String {
+    now().timestamp().as_nanosecond().to_string()
+}
+
+// prometheus style units, in milliseconds
+const DURATION_UNITS: [(&str, u64); 7] = [
+    ("y", 365 * 24 * 60 * 60 * 1000),
+    ("w", 7 * 24 * 60 * 60 * 1000),
+    ("d", 24 * 60 * 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("m", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+// "1h30m" and "1h 30m" become "5400", sub-second remainders are kept as a decimal
+pub fn parse_duration(input: &str) -> Result<String> {
+    let input = input.trim();
+    let mut chars = input.chars().peekable();
+    let mut total: u64 = 0;
+
+    if input.is_empty() {
+        return Err(Error::Input("expected a duration like 1h30m".to_string()));
+    }
+
+    // whitespace may separate parts, but never joins two numbers into one
+    let skip_whitespace = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    };
+
+    while chars.peek().is_some() {
+        let amount = std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect::<String>();
+        skip_whitespace(&mut chars);
+        let unit =
+            std::iter::from_fn(|| chars.next_if(|c| !c.is_ascii_digit() && !c.is_whitespace()))
+                .collect::<String>();
+        skip_whitespace(&mut chars);
+
+        if amount.is_empty() {
+            return Err(Error::Input(format!("expected a number before {unit:?}")));
+        }
+
+        if unit.is_empty() {
+            return Err(Error::Input(format!("missing a unit after {amount}")));
+        }
+
+        let (_, millis) = DURATION_UNITS
+            .iter()
+            .find(|(suffix, _)| *suffix == unit)
+            .ok_or_else(|| {
+                Error::Input(format!(
+                    "unrecognised unit {unit:?} after {amount}, expected one of y, w, d, h, m, s, ms"
+                ))
+            })?;
+
+        total = amount
+            .parse::<u64>()?
+            .checked_mul(*millis)
+            .and_then(|millis| total.checked_add(millis))
+            .ok_or_else(|| Error::Input(format!("{input:?} is too large")))?;
+    }
+
+    match total % 1000 {
+        0 => Ok((total / 1000).to_string()),
+        millis => Ok(format!("{}.{millis:03}", total / 1000)
+            .trim_end_matches('0')
+            .to_string()),
+    }
+}
+
+// "5400" becomes "1h30m"
+pub fn format_duration(input: &str) -> Result<String> {
+    let mut remaining = input.trim().parse::<u64>()?;
+
+    if remaining == 0 {
+        return Ok("0s".to_string());
+    }
+
+    let mut output = String::new();
+    for (suffix, seconds) in [("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)] {
+        if remaining >= seconds {
+            output.push_str(&format!("{}{suffix}", remaining / seconds));
+            remaining %= seconds;
+        }
+    }
+
+    Ok(output)
+}
+
+// guess the unit from the magnitude, anything below 1e11 is seconds (up until the year 5138)
+fn parse_epoch(input: &str) -> Result<jiff::Timestamp> {
+    let epoch = input.trim().parse::<i64>()?;
+
+    if epoch < 0 {
+        return Err(Error::Input(format!(
+            "{epoch} is negative, expected a unix timestamp"
+        )));
+    }
+
+    let timestamp = match epoch {
+        0..100_000_000_000 => jiff::Timestamp::from_second(epoch)?,
+        100_000_000_000..100_000_000_000_000 => jiff::Timestamp::from_millisecond(epoch)?,
+        100_000_000_000_000..100_000_000_000_000_000 => jiff::Timestamp::from_microsecond(epoch)?,
+        _ => jiff::Timestamp::from_nanosecond(epoch as i128)?,
+    };
+
+    Ok(timestamp)
+}
+
+pub fn parse_timestamp(input: &str) -> Result<String> {
+    Ok(parse_epoch(input)?.to_string())
+}
+
+// dst transitions make some local times happen twice and others not at all, rather than
+// guessing ask for an offset
+fn civil_to_timestamp(
+    datetime: jiff::civil::DateTime,
+    tz: &jiff::tz::TimeZone,
+) -> Result<jiff::Timestamp> {
+    let zone = tz.iana_name().unwrap_or("the timezone");
+
+    match tz.to_ambiguous_timestamp(datetime).offset() {
+        jiff::tz::AmbiguousOffset::Unambiguous { offset } => Ok(offset.to_timestamp(datetime)?),
+        jiff::tz::AmbiguousOffset::Gap { before, after } => Err(Error::Input(format!(
+            "{datetime} doesn't exist in {zone}, the clocks change from {before} to {after}"
+        ))),
+        jiff::tz::AmbiguousOffset::Fold { before, after } => Err(Error::Input(format!(
+            "{datetime} happens twice in {zone}, add an offset of {before} or {after} to pick one"
+        ))),
+    }
+}
+
+// an epoch, an rfc 3339/9557 timestamp, or a civil date(time) in the given timezone
+fn parse_any_timestamp(input: &str, tz: &jiff::tz::TimeZone) -> Result<jiff::Timestamp> {
+    let input = input.trim();
+
+    if input.parse::<i64>().is_ok() {
+        return parse_epoch(input);
+    }
+
+    if let Ok(timestamp) = input.parse::<jiff::Timestamp>() {
+        return Ok(timestamp);
+    }
+
+    if let Ok(zoned) = input.parse::<jiff::Zoned>() {
+        return Ok(zoned.timestamp());
+    }
+
+    if let Ok(datetime) = input.parse::<jiff::civil::DateTime>() {
+        return civil_to_timestamp(datetime, tz);
+    }
+
+    Err(Error::Input(format!(
+        "{input:?} is not a recognised timestamp"
+    )))
+}
+
+pub fn relative_timestamp(input: &str) -> Result<String> {
+    let now = now();
+    let then =
+        parse_any_timestamp(input, &jiff::tz::TimeZone::UTC)?.to_zoned(jiff::tz::TimeZone::UTC);
+    let span = now.until((jiff::Unit::Year, &then))?;
+
+    // spans are calendar aware, so the largest non-zero unit is the one to report
+    let units = [
+        (span.get_years() as i64, "year"),
+        (span.get_months() as i64, "month"),
+        (span.get_days() as i64 / 7, "week"),
+        (span.get_days() as i64, "day"),
+        (span.get_hours() as i64, "hour"),
+        (span.get_minutes(), "minute"),
+        (span.get_seconds(), "second"),
+    ];
+
+    let Some((amount, unit)) = units.into_iter().find(|(amount, _)| *amount != 0) else {
+        return Ok("just now".to_string());
+    };
+
+    let plural = match amount.abs() {
+        1 => "",
+        _ => "s",
+    };
+
+    match amount.is_negative() {
+        true => Ok(format!("{} {unit}{plural} ago", amount.abs())),
+        false => Ok(format!("in {amount} {unit}{plural}")),
+    }
+}
+
+// only inputs without an offset are read in `from`, the rest already say where they are
+pub fn convert_timezone(input: &str, from: &str, to: &str) -> Result<String> {
+    let from = jiff::tz::TimeZone::get(from)?;
+    let to = jiff::tz::TimeZone::get(to)?;
+
+    Ok(parse_any_timestamp(input, &from)?.to_zoned(to).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_allows_spaces_between_units() {
+        assert_eq!(parse_duration("1h30m").unwrap(), "5400");
+        assert_eq!(parse_duration("1h 30m").unwrap(), "5400");
+        assert_eq!(parse_duration(" 1 h 30 m 500ms ").unwrap(), "5400.5");
+        assert!(parse_duration("1 30m").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Length,
+    Temperature,
+    Weight,
+}
+
+impl Family {
+    fn name(self) -> &'static str {
+        match self {
+            Family::Length => "length",
+            Family::Temperature => "temperature",
+            Family::Weight => "weight",
+        }
+    }
+}
+
+struct Unit {
+    symbol: &'static str,
+    // matched case-insensitively, the symbol is always accepted too
+    aliases: &'static [&'static str],
+    family: Family,
+    // converts to the family's base unit, metres, kilograms or kelvin, as value * scale + offset
+    scale: f64,
+    offset: f64,
+}
+
+const fn unit(
+    symbol: &'static str,
+    aliases: &'static [&'static str],
+    family: Family,
+    scale: f64,
+) -> Unit {
+    Unit {
+        symbol,
+        aliases,
+        family,
+        scale,
+        offset: 0.0,
+    }
+}
+
+const UNITS: [Unit; 20] = [
+    unit(
+        "mm",
+        &["millimeter", "millimeters", "millimetre", "millimetres"],
+        Family::Length,
+        0.001,
+    ),
+    unit(
+        "cm",
+        &["centimeter", "centimeters", "centimetre", "centimetres"],
+        Family::Length,
+        0.01,
+    ),
+    unit(
+        "m",
+        &["meter", "meters", "metre", "metres"],
+        Family::Length,
+        1.0,
+    ),
+    unit(
+        "km",
+        &["kilometer", "kilometers", "kilometre", "kilometres"],
+        Family::Length,
+        1000.0,
+    ),
+    unit("in", &["\"", "inch", "inches"], Family::Length, 0.0254),
+    unit("ft", &["'", "foot", "feet"], Family::Length, 0.3048),
+    unit("yd", &["yard", "yards"], Family::Length, 0.9144),
+    unit("mi", &["mile", "miles"], Family::Length, 1609.344),
+    unit(
+        "nmi",
+        &["nautical mile", "nautical miles"],
+        Family::Length,
+        1852.0,
+    ),
+    unit("mg", &["milligram", "milligrams"], Family::Weight, 0.000001),
+    unit("g", &["gram", "grams"], Family::Weight, 0.001),
+    unit(
+        "kg",
+        &["kilo", "kilos", "kilogram", "kilograms"],
+        Family::Weight,
+        1.0,
+    ),
+    unit("t", &["tonne", "tonnes"], Family::Weight, 1000.0),
+    unit("oz", &["ounce", "ounces"], Family::Weight, 0.028349523125),
+    unit(
+        "lb",
+        &["lbs", "pound", "pounds"],
+        Family::Weight,
+        0.45359237,
+    ),
+    unit("st", &["stone", "stones"], Family::Weight, 6.35029318),
+    Unit {
+        symbol: "°C",
+        aliases: &["c", "celsius", "degc"],
+        family: Family::Temperature,
+        scale: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        symbol: "°F",
+        aliases: &["f", "fahrenheit", "degf"],
+        family: Family::Temperature,
+        scale: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+    },
+    Unit {
+        symbol: "K",
+        aliases: &["kelvin"],
+        family: Family::Temperature,
+        scale: 1.0,
+        offset: 0.0,
+    },
+    Unit {
+        symbol: "°R",
+        aliases: &["r", "rankine"],
+        family: Family::Temperature,
+        scale: 5.0 / 9.0,
+        offset: 0.0,
+    },
+];
+
+// significant figures kept in the result, enough for a recipe or a map without float noise
+const SIGNIFICANT_DIGITS: i32 = 6;
+
+fn find_unit(input: &str) -> Result<&'static Unit> {
+    let name = input.trim().to_lowercase();
+
+    UNITS
+        .iter()
+        .find(|unit| unit.symbol.to_lowercase() == name || unit.aliases.contains(&name.as_str()))
+        .ok_or_else(|| {
+            Error::Input(format!(
+                "{:?} is not a known unit, expected one of {}",
+                input.trim(),
+                UNITS.map(|unit| unit.symbol).join(", ")
+            ))
+        })
+}
+
+// the number comes first, with or without a space before the unit, like `100C` or `5 km`
+fn parse_quantity(input: &str) -> Result<(f64, &'static Unit)> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+        .unwrap_or(input.len());
+    let (value, unit) = input.split_at(split);
+
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| Error::Input(format!("{input:?} doesn't start with a number")))?;
+
+    Ok((value, find_unit(unit)?))
+}
+
+fn round_significant(value: f64) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+
+    let decimals = SIGNIFICANT_DIGITS - 1 - value.abs().log10().floor() as i32;
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+pub fn convert_units(input: &str, to: &str) -> Result<String> {
+    let (value, from) = parse_quantity(input)?;
+    let to = find_unit(to)?;
+
+    if from.family != to.family {
+        return Err(Error::Input(format!(
+            "can't convert {} ({}) to {} ({})",
+            from.symbol,
+            from.family.name(),
+            to.symbol,
+            to.family.name()
+        )));
+    }
+
+    let base = value * from.scale + from.offset;
+    let converted = round_significant((base - to.offset) / to.scale);
+
+    // rounding can leave a negative zero, which isn't worth showing
+    Ok(format!("{} {}", converted + 0.0, to.symbol))
+}
@@ -0,0 +1,37 @@
+use crate::{Error, Result};
+use quick_xml::events::Event;
+
+// whitespace between tags is dropped and regenerated, text, attributes, comments and cdata
+// sections are written back exactly as they were
+pub fn format_xml(input: &str) -> Result<String> {
+    let mut reader = quick_xml::Reader::from_str(input.trim());
+
+    let mut writer = quick_xml::Writer::new_with_indent(Vec::new(), b' ', 2);
+    let mut depth = 0usize;
+
+    loop {
+        let event = reader.read_event().map_err(|err| {
+            Error::Input(format!(
+                "malformed xml at byte {}, {err}",
+                reader.error_position()
+            ))
+        })?;
+
+        match &event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof if depth > 0 => {
+                return Err(Error::Input(format!(
+                    "malformed xml, {depth} element(s) left unclosed"
+                )));
+            }
+            Event::Eof => break,
+            Event::Text(text) if text.trim().is_empty() => continue,
+            _ => {}
+        }
+
+        writer.write_event(event)?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner())?)
+}